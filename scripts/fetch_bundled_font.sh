@@ -0,0 +1,52 @@
+#!/bin/bash
+
+# 📦 내장 폰트(bundled-font 기능)용 NanumGothic 다운로드
+# 사용법: ./scripts/fetch_bundled_font.sh  →  npm run tauri build -- --features bundled-font
+#
+# google/fonts 의 main 브랜치는 계속 바뀌므로 고정한 커밋에서 받고, 받은 파일의 sha256 을 확인합니다.
+# 폰트를 갱신할 때는 FONT_COMMIT 과 FONT_SHA256 을 함께 바꿉니다.
+#   sha256: curl -fsSL "<FONT_URL>" | shasum -a 256
+set -e
+
+FONT_DIR="$(cd "$(dirname "$0")/.." && pwd)/src-tauri/fonts"
+FONT_FILE="$FONT_DIR/NanumGothic-Regular.ttf"
+FONT_COMMIT="<google/fonts 커밋 SHA (40자)>"
+FONT_SHA256="<NanumGothic-Regular.ttf 의 sha256 (64자)>"
+FONT_URL="https://raw.githubusercontent.com/google/fonts/$FONT_COMMIT/ofl/nanumgothic/NanumGothic-Regular.ttf"
+
+if ! [[ "$FONT_COMMIT" =~ ^[0-9a-f]{40}$ && "$FONT_SHA256" =~ ^[0-9a-f]{64}$ ]]; then
+  echo "❌ FONT_COMMIT / FONT_SHA256 이 지정되지 않았습니다. 고정할 커밋과 해시를 스크립트에 적어 주세요." >&2
+  exit 1
+fi
+
+# macOS 는 shasum, Linux / Windows(Git Bash) 는 sha256sum
+file_sha256() {
+  if command -v sha256sum > /dev/null; then
+    sha256sum "$1" | cut -d ' ' -f 1
+  else
+    shasum -a 256 "$1" | cut -d ' ' -f 1
+  fi
+}
+
+mkdir -p "$FONT_DIR"
+
+if [ -f "$FONT_FILE" ]; then
+  if [ "$(file_sha256 "$FONT_FILE")" = "$FONT_SHA256" ]; then
+    echo "✅ 이미 내장 폰트가 있습니다: $FONT_FILE"
+    exit 0
+  fi
+  echo "⚠️ 기존 폰트의 sha256 이 다릅니다. 다시 받습니다."
+fi
+
+echo "🔤 NanumGothic 다운로드 중... (google/fonts@${FONT_COMMIT:0:7})"
+curl -fsSL "$FONT_URL" -o "$FONT_FILE.download"
+
+ACTUAL_SHA256="$(file_sha256 "$FONT_FILE.download")"
+if [ "$ACTUAL_SHA256" != "$FONT_SHA256" ]; then
+  rm -f "$FONT_FILE.download"
+  echo "❌ sha256 이 일치하지 않습니다. (예상: $FONT_SHA256, 실제: $ACTUAL_SHA256)" >&2
+  exit 1
+fi
+
+mv "$FONT_FILE.download" "$FONT_FILE"
+echo "✅ 다운로드 완료: $FONT_FILE (SIL OFL 1.1, fonts/OFL.txt 참고)"
@@ -0,0 +1,450 @@
+// 여러 이미지 일괄 처리
+//
+// 작업 목록(이미지 경로 + 이미지별 텍스트)과 공통 설정을 한 번에 받아 CPU 코어 수만큼
+// 병렬로 처리합니다. 이미지마다 한 번만 디코딩하고, 폰트 크기와 줄바꿈 너비처럼 해상도에
+// 따라 달라지는 값은 디코딩한 이미지 크기로 Rust 에서 계산합니다.
+// 작업마다 진행 이벤트를 보내고, 취소하면 아직 저장하지 않은 이미지는 건너뜁니다.
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::fonts::FontRegistry;
+use crate::output::{ClaimedTargets, OutputOptions, OverwritePolicy, SaveOutcome};
+use crate::placement::{PlacementOptions, TextPosition};
+use crate::sizing::FontSizing;
+use crate::style::{TextStyle, TextStyleOptions};
+use crate::tile::TileOptions;
+use crate::watermark::{LogoCache, WatermarkOptions};
+use crate::ImageSettings;
+
+// 진행 상황 이벤트 이름 (프론트엔드에서 listen)
+pub const PROGRESS_EVENT: &str = "batch-progress";
+const CANCELLED_REASON: &str = "사용자가 취소했습니다.";
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchJob {
+    pub image_path: String,
+    pub text: String,
+    // 이 작업만 다른 덮어쓰기 정책을 쓸 때 (없으면 공통 설정)
+    #[serde(default)]
+    pub overwrite: Option<OverwritePolicy>,
+    // 출력 폴더 아래에 만들 하위 폴더 (입력 폴더 구조 유지, get_image_files 의 subfolder)
+    #[serde(default)]
+    pub subfolder: Option<String>,
+}
+
+// 모든 작업에 공통으로 적용되는 설정 (process_image 인자와 같은 의미)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOptions {
+    // 출력 폴더 (outputPath), 덮어쓰기 정책 (overwrite), 파일명 템플릿 (fileNameTemplate)
+    #[serde(flatten)]
+    pub output: OutputOptions,
+    // font_sizing 이 없을 때 사용하는 px 크기
+    #[serde(default)]
+    pub font_size: f32,
+    #[serde(default)]
+    pub font_sizing: Option<FontSizing>,
+    #[serde(default)]
+    pub position_x: f32,
+    #[serde(default)]
+    pub position_y: f32,
+    #[serde(default)]
+    pub placement: Option<PlacementOptions>,
+    #[serde(default)]
+    pub font_id: Option<String>,
+    #[serde(default)]
+    pub style: TextStyleOptions,
+    // 텍스트와 함께 넣을 로고 (없으면 텍스트만)
+    #[serde(default)]
+    pub watermark: Option<WatermarkOptions>,
+    // 이미지 전체에 까는 반복 워터마크 (없으면 사용 안 함)
+    #[serde(default)]
+    pub tile: Option<TileOptions>,
+    // 동시에 처리할 이미지 수 (없으면 CPU 코어 수)
+    #[serde(default)]
+    pub threads: Option<usize>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchItemStatus {
+    Succeeded,
+    Failed,
+    // 취소되었거나 덮어쓰기 정책에 따라 처리하지 않음 (파일을 쓰지 않음)
+    Skipped,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchItemResult {
+    pub image_path: String,
+    pub status: BatchItemStatus,
+    // 실패 또는 건너뛴 이유
+    pub error: Option<String>,
+    // 실제로 텍스트를 그리는 데 사용한 폰트
+    pub font_used: Option<String>,
+    // 실제로 저장한 파일 경로 (auto-rename 이면 번호가 붙은 이름)
+    pub output_file: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    // 중간에 취소되었는지
+    pub cancelled: bool,
+    // 작업 목록과 같은 순서
+    pub results: Vec<BatchItemResult>,
+}
+
+// 진행 이벤트 종류 (started 다음에 succeeded / failed / skipped 중 하나, 취소로 건너뛴 작업은 skipped 만)
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchEventKind {
+    Started,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+// 작업마다 보내는 진행 상황 (전체 개수와 남은 시간 포함)
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchProgress {
+    pub kind: BatchEventKind,
+    // 작업 목록에서의 위치
+    pub index: usize,
+    pub image_path: String,
+    pub error: Option<String>,
+    pub font_used: Option<String>,
+    pub total: usize,
+    // 끝난 작업 수 (succeeded + failed + skipped)
+    pub completed: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub elapsed_ms: u64,
+    // 지금까지 처리 속도로 계산한 남은 시간 (아직 끝난 작업이 없으면 None)
+    pub eta_ms: Option<u64>,
+}
+
+// 진행 중인 일괄 처리의 취소 상태 (앱 전체에서 한 번에 하나만 실행)
+#[derive(Default)]
+pub struct BatchControl {
+    running: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl BatchControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 진행 중인 일괄 처리가 있으면 취소 요청 (처리 중인 이미지는 저장하기 전에 멈춤)
+    pub fn cancel(&self) -> bool {
+        let running = self.running.load(Ordering::SeqCst);
+        if running {
+            self.cancelled.store(true, Ordering::SeqCst);
+        }
+        running
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn begin(&self) -> Result<RunningBatch<'_>, String> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return Err("이미 일괄 처리가 진행 중입니다.".to_string());
+        }
+        self.cancelled.store(false, Ordering::SeqCst);
+        Ok(RunningBatch(self))
+    }
+}
+
+// 일괄 처리가 끝나면 (오류로 끝나도) 실행 중 표시를 해제
+struct RunningBatch<'a>(&'a BatchControl);
+
+impl Drop for RunningBatch<'_> {
+    fn drop(&mut self) {
+        self.0.running.store(false, Ordering::SeqCst);
+    }
+}
+
+// 작업별 결과 집계 (이벤트마다 일관된 개수를 보내기 위해 잠금 사용)
+struct ProgressTracker {
+    total: usize,
+    started_at: Instant,
+    counts: Mutex<(usize, usize, usize)>,
+}
+
+impl ProgressTracker {
+    fn new(total: usize) -> Self {
+        ProgressTracker {
+            total,
+            started_at: Instant::now(),
+            counts: Mutex::new((0, 0, 0)),
+        }
+    }
+
+    fn event(&self, kind: BatchEventKind, index: usize, job: &BatchJob, result: Option<&BatchItemResult>) -> BatchProgress {
+        let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        match kind {
+            BatchEventKind::Started => {}
+            BatchEventKind::Succeeded => counts.0 += 1,
+            BatchEventKind::Failed => counts.1 += 1,
+            BatchEventKind::Skipped => counts.2 += 1,
+        }
+        let (succeeded, failed, skipped) = *counts;
+        drop(counts);
+
+        let elapsed = self.started_at.elapsed();
+        // 건너뛴 작업은 처리 시간이 거의 없으므로 속도 계산에서 제외
+        let processed = succeeded + failed;
+        let remaining = self.total - (succeeded + failed + skipped);
+        let eta_ms = (processed > 0).then(|| (elapsed.as_millis() as f64 / processed as f64 * remaining as f64) as u64);
+
+        BatchProgress {
+            kind,
+            index,
+            image_path: job.image_path.clone(),
+            error: result.and_then(|result| result.error.clone()),
+            font_used: result.and_then(|result| result.font_used.clone()),
+            total: self.total,
+            completed: succeeded + failed + skipped,
+            succeeded,
+            failed,
+            skipped,
+            elapsed_ms: elapsed.as_millis() as u64,
+            eta_ms,
+        }
+    }
+}
+
+// 공통 설정을 먼저 검증하고 (잘못되면 전체 실패) 작업별 결과를 모아서 돌려줌
+// on_progress 는 여러 작업 스레드에서 동시에 호출됨
+pub fn run_batch(
+    fonts: &FontRegistry,
+    logos: &LogoCache,
+    control: &BatchControl,
+    jobs: &[BatchJob],
+    options: &BatchOptions,
+    on_progress: impl Fn(BatchProgress) + Sync,
+) -> Result<BatchSummary, String> {
+    if options.output.output_path.is_empty() {
+        return Err("출력 폴더가 지정되지 않았습니다.".to_string());
+    }
+    options.output.encoding.validate()?;
+
+    // 지정한 폰트를 찾을 수 없으면 다른 폰트로 대체하지 않고 실패 처리
+    let font_chain = fonts.font_chain(options.font_id.as_deref())?;
+    let sizing = FontSizing::from_args(options.font_size, options.font_sizing)?;
+    let position = TextPosition::from_args(options.position_x, options.position_y, options.placement)?;
+    // 스타일 값이 잘못되면 모든 작업이 같은 이유로 실패하므로 시작하기 전에 한 번만 확인
+    options.style.resolve(TextStyle::SIMPLE)?;
+    // 로고는 한 번만 읽어서 모든 작업이 공유
+    let (watermark, tile) = crate::load_layers(logos, options.watermark.as_ref(), options.tile.as_ref())?;
+
+    let mut pool = rayon::ThreadPoolBuilder::new();
+    if let Some(threads) = options.threads.filter(|&threads| threads > 0) {
+        pool = pool.num_threads(threads);
+    }
+    let pool = pool.build().map_err(|e| format!("작업 스레드 생성 실패: {}", e))?;
+    let claimed_targets = ClaimedTargets::new();
+
+    let settings = ImageSettings {
+        fonts: &font_chain,
+        style: &options.style,
+        sizing,
+        position,
+        output: &options.output,
+        watermark: watermark.as_ref(),
+        tile: tile.as_ref(),
+        claimed_targets: Some(&claimed_targets),
+    };
+
+    let _running = control.begin()?;
+    let tracker = ProgressTracker::new(jobs.len());
+
+    let results: Vec<BatchItemResult> = pool.install(|| {
+        jobs.par_iter()
+            .enumerate()
+            .map(|(index, job)| {
+                // 취소된 뒤에는 아직 시작하지 않은 작업을 모두 건너뜀
+                if control.is_cancelled() {
+                    let result = BatchItemResult::skipped(job, CANCELLED_REASON);
+                    on_progress(tracker.event(BatchEventKind::Skipped, index, job, Some(&result)));
+                    return result;
+                }
+
+                on_progress(tracker.event(BatchEventKind::Started, index, job, None));
+
+                let result = process_job(&settings, control, job, index);
+
+                let kind = match result.status {
+                    BatchItemStatus::Succeeded => BatchEventKind::Succeeded,
+                    BatchItemStatus::Failed => BatchEventKind::Failed,
+                    BatchItemStatus::Skipped => BatchEventKind::Skipped,
+                };
+                on_progress(tracker.event(kind, index, job, Some(&result)));
+                result
+            })
+            .collect()
+    });
+
+    let count = |status: BatchItemStatus| results.iter().filter(|result| result.status == status).count();
+    Ok(BatchSummary {
+        total: results.len(),
+        succeeded: count(BatchItemStatus::Succeeded),
+        failed: count(BatchItemStatus::Failed),
+        skipped: count(BatchItemStatus::Skipped),
+        cancelled: control.is_cancelled(),
+        results,
+    })
+}
+
+// 작업 하나 처리 결과를 일괄 처리 결과로 변환
+fn process_job(settings: &ImageSettings, control: &BatchControl, job: &BatchJob, index: usize) -> BatchItemResult {
+    match crate::process_image_internal(settings, job, index, &|| control.is_cancelled()) {
+        Ok(SaveOutcome::Written(path)) => {
+            let font_names = settings.fonts.names_used(&job.text);
+            BatchItemResult {
+                image_path: job.image_path.clone(),
+                status: BatchItemStatus::Succeeded,
+                error: None,
+                font_used: if font_names.is_empty() { None } else { Some(font_names.join(", ")) },
+                output_file: Some(path.to_string_lossy().to_string()),
+            }
+        }
+        Ok(SaveOutcome::Skipped(reason)) => BatchItemResult::skipped(job, &reason),
+        Ok(SaveOutcome::Cancelled) => BatchItemResult::skipped(job, CANCELLED_REASON),
+        Err(e) => BatchItemResult::failed(job, e),
+    }
+}
+
+impl BatchItemResult {
+    fn failed(job: &BatchJob, error: String) -> Self {
+        BatchItemResult {
+            image_path: job.image_path.clone(),
+            status: BatchItemStatus::Failed,
+            error: Some(error),
+            font_used: None,
+            output_file: None,
+        }
+    }
+
+    fn skipped(job: &BatchJob, reason: &str) -> Self {
+        BatchItemResult {
+            image_path: job.image_path.clone(),
+            status: BatchItemStatus::Skipped,
+            error: Some(reason.to_string()),
+            font_used: None,
+            output_file: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn job(image_path: &str) -> BatchJob {
+        BatchJob { image_path: image_path.to_string(), text: "테스트".to_string(), overwrite: None, subfolder: None }
+    }
+
+    fn options(output_path: &str) -> BatchOptions {
+        serde_json::from_value(serde_json::json!({ "outputPath": output_path, "fontSize": 24.0, "threads": 1 })).unwrap()
+    }
+
+    #[test]
+    fn eta_uses_processed_jobs_only() {
+        let tracker = ProgressTracker {
+            total: 5,
+            started_at: Instant::now() - Duration::from_millis(400),
+            counts: Mutex::new((0, 0, 0)),
+        };
+        let first = job("a.jpg");
+
+        // 끝난 작업이 없으면 남은 시간을 알 수 없음
+        let progress = tracker.event(BatchEventKind::Started, 0, &first, None);
+        assert_eq!(progress.eta_ms, None);
+        // 건너뛴 작업만으로는 속도를 계산하지 않음
+        let progress = tracker.event(BatchEventKind::Skipped, 0, &first, None);
+        assert_eq!((progress.completed, progress.eta_ms), (1, None));
+
+        tracker.event(BatchEventKind::Succeeded, 1, &first, None);
+        let progress = tracker.event(BatchEventKind::Failed, 2, &first, None);
+        // 2개 처리 / 2개 남음 → 지금까지 걸린 시간만큼 더 걸림
+        assert_eq!((progress.completed, progress.succeeded, progress.failed, progress.skipped), (3, 1, 1, 1));
+        let eta = progress.eta_ms.unwrap();
+        assert!(eta >= 400 && eta.abs_diff(progress.elapsed_ms) <= 1, "eta {} elapsed {}", eta, progress.elapsed_ms);
+
+        tracker.event(BatchEventKind::Succeeded, 3, &first, None);
+        let progress = tracker.event(BatchEventKind::Succeeded, 4, &first, None);
+        assert_eq!((progress.completed, progress.eta_ms), (5, Some(0)));
+    }
+
+    #[test]
+    fn invalid_style_fails_before_starting() {
+        let control = BatchControl::new();
+        let mut options = options(&std::env::temp_dir().to_string_lossy());
+        options.style.text_color = Some("#12".to_string());
+
+        let result = run_batch(&FontRegistry::new(), &LogoCache::new(), &control, &[job("a.jpg")], &options, |_| {
+            panic!("시작하지 않아야 함")
+        });
+        assert!(result.unwrap_err().contains("글자 색상"));
+        // 실행 중 표시가 남지 않음
+        assert!(!control.cancel());
+    }
+
+    #[test]
+    fn cancel_only_applies_to_running_batch() {
+        let control = BatchControl::new();
+        assert!(!control.cancel());
+
+        let running = control.begin().unwrap();
+        assert!(control.begin().is_err());
+        assert!(control.cancel());
+        assert!(control.is_cancelled());
+        drop(running);
+
+        // 새로 시작하면 이전 취소 요청은 지워짐
+        let _running = control.begin().unwrap();
+        assert!(!control.is_cancelled());
+    }
+
+    #[test]
+    fn cancelled_batch_skips_remaining_jobs() {
+        let control = BatchControl::new();
+        let output = std::env::temp_dir().join(format!("image-overlay-batch-cancel-{}", std::process::id()));
+        let options = options(&output.to_string_lossy());
+        let jobs: Vec<BatchJob> = (0..4).map(|index| job(&format!("missing-{}.jpg", index))).collect();
+
+        // 첫 작업을 시작하자마자 취소
+        let summary = run_batch(&FontRegistry::new(), &LogoCache::new(), &control, &jobs, &options, |progress| {
+            if progress.kind == BatchEventKind::Started {
+                control.cancel();
+            }
+        })
+        .unwrap();
+
+        assert!(summary.cancelled);
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.succeeded, 0);
+        let cancelled = summary.results.iter().filter(|result| result.error.as_deref() == Some(CANCELLED_REASON)).count();
+        assert!(cancelled >= 3, "{:?}", summary.results);
+        // 끝나면 다시 시작할 수 있음
+        assert!(!control.cancel());
+        assert!(control.begin().is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+// 파일 내용(매직 바이트)으로 이미지 형식 판별
+//
+// 확장자는 틀릴 수 있으므로 (".jpg" 로 저장된 PNG, 확장자 없는 파일) 디코딩과 "원본 형식" 저장은
+// 파일 앞부분으로 판별한 형식을 따르고, 확장자는 실제 형식과 맞는지 확인하는 데만 씁니다.
+
+use image::ImageFormat;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+// 형식 판별에 읽을 앞부분 크기 (image::guess_format 이 보는 가장 긴 서명보다 넉넉하게)
+const SNIFF_BYTES: usize = 64;
+
+// 처리할 수 있는 형식 (읽기 + 같은 형식으로 저장)
+const SUPPORTED_FORMATS: [ImageFormat; 6] = [
+    ImageFormat::Jpeg,
+    ImageFormat::Png,
+    ImageFormat::Bmp,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+    ImageFormat::Tiff,
+];
+
+// 파일 앞부분만 읽어서 판별 (폴더 검색, 디코딩 전 저장 형식 결정)
+pub fn sniff_file(path: &Path) -> Result<ImageFormat, String> {
+    let mut header = Vec::with_capacity(SNIFF_BYTES);
+    File::open(path)
+        .and_then(|file| file.take(SNIFF_BYTES as u64).read_to_end(&mut header))
+        .map_err(|e| format!("파일 읽기 실패: {}", e))?;
+    detect_format(&header)
+}
+
+pub fn detect_format(source: &[u8]) -> Result<ImageFormat, String> {
+    match image::guess_format(source) {
+        Ok(format) if SUPPORTED_FORMATS.contains(&format) => Ok(format),
+        Ok(format) => Err(format!("지원하지 않는 이미지 형식: {}", format_name(format))),
+        Err(_) => Err("이미지 형식을 알 수 없습니다.".to_string()),
+    }
+}
+
+// 확장자가 format 의 확장자 중 하나인지 (대소문자 무시, "jpeg" / "jpg" 모두 JPEG)
+pub fn extension_matches(path: &Path, format: ImageFormat) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| format.extensions_str().iter().any(|known| known.eq_ignore_ascii_case(ext)))
+}
+
+// format 으로 저장할 때 붙일 확장자 (".jpeg" 가 아닌 ".jpg", ".tiff" 가 아닌 ".tif")
+pub fn preferred_extension(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Jpeg => "jpg",
+        ImageFormat::Tiff => "tif",
+        _ => format.extensions_str().first().copied().unwrap_or("img"),
+    }
+}
+
+// 메시지에 쓰는 이름 ("JPEG", "PNG", "WEBP" ...)
+pub fn format_name(format: ImageFormat) -> String {
+    format!("{:?}", format).to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const JPEG: &[u8] = b"\xff\xd8\xff\xe0\0\x10JFIF\0";
+    const WEBP: &[u8] = b"RIFF\x24\0\0\0WEBPVP8 ";
+
+    #[test]
+    fn detect_format_reads_magic_bytes() {
+        assert_eq!(detect_format(PNG), Ok(ImageFormat::Png));
+        assert_eq!(detect_format(JPEG), Ok(ImageFormat::Jpeg));
+        assert_eq!(detect_format(WEBP), Ok(ImageFormat::WebP));
+        assert_eq!(detect_format(b"GIF89a\x01\0\x01\0"), Ok(ImageFormat::Gif));
+        assert_eq!(detect_format(b"BM\x3a\0\0\0"), Ok(ImageFormat::Bmp));
+        assert_eq!(detect_format(b"II\x2a\0\x08\0\0\0"), Ok(ImageFormat::Tiff));
+        assert_eq!(detect_format(b"MM\0\x2a\0\0\0\x08"), Ok(ImageFormat::Tiff));
+    }
+
+    #[test]
+    fn detect_format_rejects_unknown_and_unsupported() {
+        assert!(detect_format(b"not an image").is_err());
+        assert!(detect_format(&[]).is_err());
+        // ICO 는 image 가 알지만 처리하지 않는 형식
+        let error = detect_format(b"\0\0\x01\0\x01\0\x10\x10").unwrap_err();
+        assert!(error.contains("ICO"), "{}", error);
+    }
+
+    #[test]
+    fn extension_matches_detected_format() {
+        assert!(extension_matches(Path::new("a.png"), ImageFormat::Png));
+        assert!(extension_matches(Path::new("a.JPEG"), ImageFormat::Jpeg));
+        assert!(extension_matches(Path::new("a.jpg"), ImageFormat::Jpeg));
+        assert!(extension_matches(Path::new("a.tif"), ImageFormat::Tiff));
+    }
+
+    #[test]
+    fn extension_mismatch_is_reported() {
+        // ".jpg" 로 저장된 PNG
+        let format = detect_format(PNG).unwrap();
+        assert!(!extension_matches(Path::new("photo.jpg"), format));
+        assert!(!extension_matches(Path::new("photo"), format));
+        assert!(!extension_matches(Path::new("photo.png.bak"), format));
+        assert_eq!(preferred_extension(format), "png");
+    }
+
+    #[test]
+    fn preferred_extension_uses_short_names() {
+        assert_eq!(preferred_extension(ImageFormat::Jpeg), "jpg");
+        assert_eq!(preferred_extension(ImageFormat::Tiff), "tif");
+        assert_eq!(preferred_extension(ImageFormat::WebP), "webp");
+    }
+}
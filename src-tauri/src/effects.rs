@@ -0,0 +1,220 @@
+// 글리프 커버리지 마스크 기반 텍스트 효과 (외곽선, 그림자, 글로우)
+//
+// 배치된 글리프를 0 ~ 255 커버리지 마스크로 그린 뒤 팽창/흐림을 적용해
+// 글자 모양을 따라가는 효과 마스크를 만듭니다. 합성은 render 모듈에서 합니다.
+
+use image::{GrayImage, Luma};
+use imageproc::distance_transform::euclidean_squared_distance_transform;
+use rusttype::PositionedGlyph;
+
+// 이 값 이상이면 글자 안쪽으로 보고 외곽선 거리 계산의 기준으로 사용
+const INK_THRESHOLD: u8 = 128;
+
+pub struct CoverageMask {
+    pub image: GrayImage,
+    // 그리기 원점 (x, y) 기준 마스크 왼쪽 위 좌표
+    pub left: i32,
+    pub top: i32,
+}
+
+impl CoverageMask {
+    // 글리프 커버리지를 margin 만큼 여백을 두고 그림 (잉크가 없으면 None)
+    pub fn from_glyphs(glyphs: &[PositionedGlyph], margin: u32) -> Option<Self> {
+        let mut edges: Option<(i32, i32, i32, i32)> = None;
+        for bb in glyphs.iter().filter_map(|glyph| glyph.pixel_bounding_box()) {
+            edges = Some(match edges {
+                Some((min_x, min_y, max_x, max_y)) => {
+                    (min_x.min(bb.min.x), min_y.min(bb.min.y), max_x.max(bb.max.x), max_y.max(bb.max.y))
+                }
+                None => (bb.min.x, bb.min.y, bb.max.x, bb.max.y),
+            });
+        }
+        let (min_x, min_y, max_x, max_y) = edges?;
+
+        let left = min_x - margin as i32;
+        let top = min_y - margin as i32;
+        let width = (max_x - min_x) as u32 + margin * 2;
+        let height = (max_y - min_y) as u32 + margin * 2;
+        let mut image = GrayImage::new(width, height);
+
+        for glyph in glyphs {
+            let bb = match glyph.pixel_bounding_box() {
+                Some(bb) => bb,
+                None => continue,
+            };
+
+            glyph.draw(|gx, gy, coverage| {
+                let mask_x = (bb.min.x - left) as u32 + gx;
+                let mask_y = (bb.min.y - top) as u32 + gy;
+                let value = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+                let pixel = image.get_pixel_mut(mask_x, mask_y);
+                // 겹치는 글리프는 더 진한 쪽 사용
+                pixel.0[0] = pixel.0[0].max(value);
+            });
+        }
+
+        Some(CoverageMask { image, left, top })
+    }
+
+    // 글자 모양을 radius 만큼 둥글게 넓힘 (외곽선, 글로우용)
+    pub fn dilate(&self, radius: f32) -> CoverageMask {
+        if radius <= 0.0 {
+            return self.with_image(self.image.clone());
+        }
+
+        let ink = GrayImage::from_fn(self.image.width(), self.image.height(), |x, y| {
+            Luma([if self.image.get_pixel(x, y).0[0] >= INK_THRESHOLD { 255 } else { 0 }])
+        });
+        let distances = euclidean_squared_distance_transform(&ink);
+
+        let image = GrayImage::from_fn(self.image.width(), self.image.height(), |x, y| {
+            let distance = distances.get_pixel(x, y).0[0].sqrt() as f32;
+            // 경계 1px 은 거리에 비례해 부드럽게 처리
+            let spread = ((radius + 0.5 - distance).clamp(0.0, 1.0) * 255.0).round() as u8;
+            Luma([spread.max(self.image.get_pixel(x, y).0[0])])
+        });
+
+        self.with_image(image)
+    }
+
+    // 가우시안 흐림 (radius 는 대략 흐려지는 거리)
+    // imageproc 의 gaussian_blur_f32 는 커널을 정규화하지 않고 u8 로 내림해서 그림자가 옅어지므로
+    // 합이 1 인 커널로 직접 흐림 (마스크 밖으로 퍼지는 양만 버림)
+    pub fn blur(&self, radius: f32) -> CoverageMask {
+        if radius <= 0.0 {
+            return self.with_image(self.image.clone());
+        }
+
+        let (width, height) = self.image.dimensions();
+        let kernel = gaussian_kernel(radius / 2.0);
+        let values: Vec<f32> = self.image.pixels().map(|pixel| pixel.0[0] as f32).collect();
+        let values = blur_pass(&values, width, height, &kernel, (1, 0));
+        let values = blur_pass(&values, width, height, &kernel, (0, 1));
+
+        let image = GrayImage::from_fn(width, height, |x, y| {
+            Luma([values[(y * width + x) as usize].round().min(255.0) as u8])
+        });
+        self.with_image(image)
+    }
+
+    fn with_image(&self, image: GrayImage) -> CoverageMask {
+        CoverageMask {
+            image,
+            left: self.left,
+            top: self.top,
+        }
+    }
+}
+
+// 표준편차 sigma 인 1차원 가우시안 커널 (3σ 까지, 합이 1)
+fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    let half = (sigma * 3.0).ceil() as i32;
+    let kernel: Vec<f32> = (-half..=half).map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp()).collect();
+    let sum: f32 = kernel.iter().sum();
+    kernel.into_iter().map(|weight| weight / sum).collect()
+}
+
+// 한 방향 (step 이 (1, 0) 이면 가로, (0, 1) 이면 세로) 으로 커널을 적용
+fn blur_pass(values: &[f32], width: u32, height: u32, kernel: &[f32], step: (i32, i32)) -> Vec<f32> {
+    let half = (kernel.len() / 2) as i32;
+    let mut output = vec![0.0; values.len()];
+
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let value = values[(y * width as i32 + x) as usize];
+            // 글자 마스크는 대부분 비어 있으므로 값이 있는 픽셀만 주변으로 퍼뜨림
+            if value == 0.0 {
+                continue;
+            }
+            for (i, weight) in kernel.iter().enumerate() {
+                let offset = i as i32 - half;
+                let (tx, ty) = (x + step.0 * offset, y + step.1 * offset);
+                if (0..width as i32).contains(&tx) && (0..height as i32).contains(&ty) {
+                    output[(ty * width as i32 + tx) as usize] += value * weight;
+                }
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // size x size 마스크에 점 하나 (x, y)
+    fn dot(size: u32, x: u32, y: u32) -> CoverageMask {
+        let mut image = GrayImage::new(size, size);
+        image.put_pixel(x, y, Luma([255]));
+        CoverageMask { image, left: -3, top: 5 }
+    }
+
+    fn total(mask: &CoverageMask) -> u64 {
+        mask.image.pixels().map(|pixel| pixel.0[0] as u64).sum()
+    }
+
+    #[test]
+    fn dilate_grows_dot_to_radius() {
+        let radius = 3.0;
+        let mask = dot(21, 10, 10).dilate(radius);
+        assert_eq!((mask.left, mask.top), (-3, 5));
+
+        for (x, y, pixel) in mask.image.enumerate_pixels() {
+            let distance = ((x as f32 - 10.0).powi(2) + (y as f32 - 10.0).powi(2)).sqrt();
+            // 반지름 r 에서 절반 커버리지 (1px 에 걸쳐 부드럽게)
+            if distance <= radius - 0.5 {
+                assert_eq!(pixel.0[0], 255, "({}, {})", x, y);
+            } else if distance == radius {
+                assert_eq!(pixel.0[0], 128, "({}, {})", x, y);
+            } else if distance >= radius + 0.5 {
+                assert_eq!(pixel.0[0], 0, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn dilate_at_edge_stays_inside_mask() {
+        let mask = dot(8, 0, 0).dilate(3.0);
+        assert_eq!(mask.image.dimensions(), (8, 8));
+        assert_eq!(mask.image.get_pixel(2, 0).0[0], 255);
+        assert_eq!(mask.image.get_pixel(0, 3).0[0], 128);
+        assert_eq!(mask.image.get_pixel(4, 0).0[0], 0);
+        assert_eq!(mask.image.get_pixel(7, 7).0[0], 0);
+    }
+
+    #[test]
+    fn blur_preserves_total_coverage() {
+        let mut image = GrayImage::new(41, 41);
+        for y in 18..23 {
+            for x in 18..23 {
+                image.put_pixel(x, y, Luma([255]));
+            }
+        }
+        let mask = CoverageMask { image, left: 0, top: 0 };
+        let blurred = mask.blur(4.0);
+
+        let (before, after) = (total(&mask) as f64, total(&blurred) as f64);
+        assert!((after - before).abs() / before < 0.01, "{} -> {}", before, after);
+        // 가운데는 옅어지고 주변으로 퍼짐
+        assert!(blurred.image.get_pixel(20, 20).0[0] < 255);
+        assert!(blurred.image.get_pixel(15, 20).0[0] > 0);
+    }
+
+    #[test]
+    fn blur_at_edge_keeps_mask_size() {
+        let blurred = dot(8, 7, 7).blur(2.0);
+        assert_eq!(blurred.image.dimensions(), (8, 8));
+        assert!(blurred.image.get_pixel(7, 7).0[0] > 0);
+        assert!(blurred.image.get_pixel(6, 7).0[0] > 0);
+    }
+
+    #[test]
+    fn zero_radius_keeps_mask() {
+        let mask = dot(5, 2, 2);
+        for effect in [mask.dilate(0.0), mask.blur(0.0), mask.dilate(-1.0), mask.blur(-1.0)] {
+            assert_eq!(effect.image, mask.image);
+            assert_eq!((effect.left, effect.top), (mask.left, mask.top));
+        }
+    }
+}
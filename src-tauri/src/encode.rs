@@ -0,0 +1,271 @@
+// 결과 이미지 인코딩 (출력 형식 + 형식별 옵션)
+//
+// 출력 형식을 입력 파일과 다르게 지정할 수 있습니다 (JPEG 원본 → WebP 등).
+// JPEG 은 jpeg-encoder (품질, 프로그레시브, 크로마 서브샘플링), WebP 는 libwebp (손실/무손실)로
+// 인코딩하고, AVIF 는 avif 기능을 켠 빌드에서만 지원합니다.
+// 원본 메타데이터는 JPEG / PNG / WebP 로 저장할 때만 함께 씁니다.
+
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+use serde::Deserialize;
+use std::io::{Cursor, Seek, Write};
+
+use crate::metadata::{self, ImageMetadata};
+
+// 기존 JPEG 저장 품질
+const DEFAULT_JPEG_QUALITY: u8 = 90;
+const DEFAULT_WEBP_QUALITY: u8 = 80;
+#[cfg(feature = "avif")]
+const DEFAULT_AVIF_QUALITY: u8 = 80;
+// AVIF 인코딩 속도 (1 느림/작은 파일 ~ 10 빠름)
+#[cfg(feature = "avif")]
+const AVIF_SPEED: u8 = 6;
+// libwebp 가 처리할 수 있는 최대 너비/높이
+const WEBP_MAX_DIMENSION: u32 = 16383;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    // 입력 파일과 같은 형식 (기존 동작)
+    #[default]
+    Original,
+    #[serde(alias = "jpg")]
+    Jpeg,
+    Png,
+    Webp,
+    #[serde(alias = "tif")]
+    Tiff,
+    Avif,
+}
+
+// JPEG 크로마 서브샘플링 (기존 image 인코더는 모든 성분을 1x1 로 저장했으므로 기본 4:4:4)
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChromaSubsampling {
+    #[default]
+    #[serde(rename = "4:4:4")]
+    Full,
+    #[serde(rename = "4:2:2")]
+    Half,
+    #[serde(rename = "4:2:0")]
+    Quarter,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EncodeOptions {
+    pub format: OutputFormat,
+    // 손실 압축 품질 1 ~ 100 (JPEG 기본 90, WebP/AVIF 기본 80)
+    pub quality: Option<u8>,
+    // JPEG
+    pub progressive: bool,
+    pub chroma_subsampling: ChromaSubsampling,
+    // PNG 압축 수준 0 ~ 9 (0 ~ 2 빠름, 3 ~ 6 보통, 7 ~ 9 최대)
+    pub png_compression: Option<u8>,
+    // WebP 무손실 (quality 무시)
+    pub webp_lossless: bool,
+}
+
+impl EncodeOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(quality) = self.quality {
+            if !(1..=100).contains(&quality) {
+                return Err(format!("품질은 1 ~ 100 사이여야 합니다: {}", quality));
+            }
+        }
+        if let Some(level) = self.png_compression {
+            if level > 9 {
+                return Err(format!("PNG 압축 수준은 0 ~ 9 사이여야 합니다: {}", level));
+            }
+        }
+        if self.format == OutputFormat::Avif && !cfg!(feature = "avif") {
+            return Err("AVIF 저장은 avif 기능을 포함한 빌드에서만 지원합니다.".to_string());
+        }
+        Ok(())
+    }
+
+    // 실제로 저장할 형식 (original 이면 입력 형식)
+    pub fn output_format(&self, input_format: ImageFormat) -> ImageFormat {
+        match self.format {
+            OutputFormat::Original => input_format,
+            OutputFormat::Jpeg => ImageFormat::Jpeg,
+            OutputFormat::Png => ImageFormat::Png,
+            OutputFormat::Webp => ImageFormat::WebP,
+            OutputFormat::Tiff => ImageFormat::Tiff,
+            OutputFormat::Avif => ImageFormat::Avif,
+        }
+    }
+}
+
+// format 으로 인코딩해서 (메타데이터와 함께) writer 에 씀
+pub fn encode_image<W: Write + Seek>(
+    img: &DynamicImage,
+    format: ImageFormat,
+    options: &EncodeOptions,
+    metadata: &ImageMetadata,
+    writer: &mut W,
+) -> Result<(), String> {
+    match format {
+        ImageFormat::Jpeg => encode_jpeg(img, options, metadata, writer),
+        // 인코딩한 뒤 메타데이터 청크를 끼워 넣음
+        ImageFormat::Png | ImageFormat::WebP if !metadata.is_empty() => {
+            let mut encoded = Cursor::new(Vec::new());
+            encode_pixels(img, format, options, &mut encoded)?;
+            metadata.write_into(encoded.into_inner(), format, writer)
+        }
+        _ => encode_pixels(img, format, options, writer),
+    }
+}
+
+fn encode_pixels<W: Write + Seek>(
+    img: &DynamicImage,
+    format: ImageFormat,
+    options: &EncodeOptions,
+    writer: &mut W,
+) -> Result<(), String> {
+    match format {
+        ImageFormat::Png => {
+            let compression = match options.png_compression {
+                Some(0..=2) => CompressionType::Fast,
+                Some(3..=6) | None => CompressionType::Default,
+                Some(_) => CompressionType::Best,
+            };
+            img.write_with_encoder(PngEncoder::new_with_quality(writer, compression, FilterType::Adaptive))
+                .map_err(|e| format!("PNG 인코딩 실패: {}", e))
+        }
+        ImageFormat::WebP => encode_webp(img, options, writer),
+        #[cfg(feature = "avif")]
+        ImageFormat::Avif => {
+            let quality = options.quality.unwrap_or(DEFAULT_AVIF_QUALITY);
+            let encoder = image::codecs::avif::AvifEncoder::new_with_speed_quality(writer, AVIF_SPEED, quality);
+            img.write_with_encoder(encoder)
+                .map_err(|e| format!("AVIF 인코딩 실패: {}", e))
+        }
+        _ => img
+            .write_to(writer, format)
+            .map_err(|e| format!("이미지 저장 실패: {}", e)),
+    }
+}
+
+fn encode_jpeg<W: Write>(
+    img: &DynamicImage,
+    options: &EncodeOptions,
+    metadata: &ImageMetadata,
+    writer: &mut W,
+) -> Result<(), String> {
+    let (width, height) = (img.width(), img.height());
+    if width > u16::MAX as u32 || height > u16::MAX as u32 {
+        return Err(format!("JPEG 은 {}px 보다 큰 이미지를 저장할 수 없습니다.", u16::MAX));
+    }
+
+    let mut encoder = jpeg_encoder::Encoder::new(writer, options.quality.unwrap_or(DEFAULT_JPEG_QUALITY));
+    encoder.set_progressive(options.progressive);
+    encoder.set_sampling_factor(match options.chroma_subsampling {
+        ChromaSubsampling::Full => jpeg_encoder::SamplingFactor::R_4_4_4,
+        ChromaSubsampling::Half => jpeg_encoder::SamplingFactor::R_4_2_2,
+        ChromaSubsampling::Quarter => jpeg_encoder::SamplingFactor::R_4_2_0,
+    });
+
+    // 원본 메타데이터 (APP1 EXIF / XMP, APP2 ICC), 세그먼트 크기를 넘으면 그 항목만 빼고 저장
+    if let Some(exif) = &metadata.exif {
+        if let Err(e) = encoder.add_app_segment(1, &[metadata::EXIF_PREFIX, exif].concat()) {
+            eprintln!("EXIF 를 저장하지 못했습니다: {}", e);
+        }
+    }
+    if let Some(xmp) = &metadata.xmp {
+        if let Err(e) = encoder.add_app_segment(1, &[metadata::XMP_JPEG_HEADER, xmp].concat()) {
+            eprintln!("XMP 를 저장하지 못했습니다: {}", e);
+        }
+    }
+    if let Some(icc) = &metadata.icc {
+        if let Err(e) = encoder.add_icc_profile(icc) {
+            eprintln!("색상 프로필을 저장하지 못했습니다: {}", e);
+        }
+    }
+
+    // JPEG 은 알파 채널이 없으므로 투명한 부분은 흰 배경에 합성 (회색조 + 알파도 합성한 뒤 회색조로)
+    let result = if img.color().has_color() {
+        encoder.encode(flatten_alpha(img).as_raw(), width as u16, height as u16, jpeg_encoder::ColorType::Rgb)
+    } else {
+        let luma = DynamicImage::ImageRgb8(flatten_alpha(img)).to_luma8();
+        encoder.encode(luma.as_raw(), width as u16, height as u16, jpeg_encoder::ColorType::Luma)
+    };
+    result.map_err(|e| format!("JPEG 인코딩 실패: {}", e))
+}
+
+fn encode_webp<W: Write>(img: &DynamicImage, options: &EncodeOptions, writer: &mut W) -> Result<(), String> {
+    let (width, height) = (img.width(), img.height());
+    if width > WEBP_MAX_DIMENSION || height > WEBP_MAX_DIMENSION {
+        return Err(format!("WebP 는 {}px 보다 큰 이미지를 저장할 수 없습니다.", WEBP_MAX_DIMENSION));
+    }
+
+    // libwebp 는 8비트 RGB / RGBA 만 받음
+    let encoded = if img.color().has_alpha() {
+        let rgba = img.to_rgba8();
+        webp::Encoder::from_rgba(rgba.as_raw(), width, height).encode_simple(options.webp_lossless, webp_quality(options))
+    } else {
+        let rgb = img.to_rgb8();
+        webp::Encoder::from_rgb(rgb.as_raw(), width, height).encode_simple(options.webp_lossless, webp_quality(options))
+    };
+    let encoded = encoded.map_err(|e| format!("WebP 인코딩 실패: {:?}", e))?;
+
+    writer
+        .write_all(&encoded)
+        .map_err(|e| format!("파일 쓰기 실패: {}", e))
+}
+
+fn webp_quality(options: &EncodeOptions) -> f32 {
+    options.quality.unwrap_or(DEFAULT_WEBP_QUALITY) as f32
+}
+
+// 알파 채널이 있으면 흰 배경에 합성한 RGB, 없으면 그대로 RGB 로 변환
+fn flatten_alpha(img: &DynamicImage) -> RgbImage {
+    if !img.color().has_alpha() {
+        return img.to_rgb8();
+    }
+
+    let rgba = img.to_rgba8();
+    RgbImage::from_fn(img.width(), img.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let alpha = a as u32;
+        let blend = |c: u8| ((c as u32 * alpha + 255 * (255 - alpha) + 127) / 255) as u8;
+        Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayAlphaImage, LumaA};
+
+    fn encode_jpeg_and_decode(img: &DynamicImage, options: &EncodeOptions) -> DynamicImage {
+        let mut encoded = Vec::new();
+        encode_jpeg(img, options, &ImageMetadata::default(), &mut encoded).unwrap();
+        image::load_from_memory_with_format(&encoded, ImageFormat::Jpeg).unwrap()
+    }
+
+    #[test]
+    fn default_chroma_subsampling_is_full() {
+        assert_eq!(EncodeOptions::default().chroma_subsampling, ChromaSubsampling::Full);
+        let options: EncodeOptions = serde_json::from_str(r#"{"format":"jpeg"}"#).unwrap();
+        assert_eq!(options.chroma_subsampling, ChromaSubsampling::Full);
+    }
+
+    #[test]
+    fn flatten_alpha_blends_onto_white() {
+        let img = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(3, 1, |x, _| {
+            image::Rgba([0, 0, 0, [0, 128, 255][x as usize]])
+        }));
+        let flattened = flatten_alpha(&img);
+        assert_eq!(flattened.get_pixel(0, 0), &Rgb([255, 255, 255]));
+        assert_eq!(flattened.get_pixel(1, 0), &Rgb([127, 127, 127]));
+        assert_eq!(flattened.get_pixel(2, 0), &Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn transparent_gray_alpha_becomes_white_in_jpeg() {
+        // 투명한 부분에 검정이 저장된 회색조 + 알파
+        let img = DynamicImage::ImageLumaA8(GrayAlphaImage::from_pixel(16, 16, LumaA([0, 0])));
+        let decoded = encode_jpeg_and_decode(&img, &EncodeOptions::default()).to_luma8();
+        assert!(decoded.pixels().all(|pixel| pixel.0[0] >= 250), "{:?}", decoded.get_pixel(0, 0));
+    }
+}
@@ -0,0 +1,330 @@
+// 결과 파일명 템플릿
+//
+// "{stem}_labeled.{ext}", "{index:04}_{date}.{ext}", "{text}_{width}x{height}.{ext}" 처럼
+// 입력 파일명, 작업 순서, 날짜, 텍스트, 이미지 크기로 파일명을 만듭니다.
+// 만든 이름은 Windows 에서도 쓸 수 있도록 금지 문자와 예약된 이름을 바꿉니다.
+
+use chrono::format::{Item, StrftimeItems};
+use std::path::Path;
+
+use crate::unicode;
+
+// {date} 기본 형식
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+// {text} 에 넣을 최대 글자 수
+const MAX_TEXT_CHARS: usize = 50;
+// 파일명 최대 길이 (바이트, 대부분의 파일 시스템 제한 255 보다 여유 있게)
+const MAX_FILE_NAME_BYTES: usize = 200;
+// Windows 예약 이름 (확장자와 관계없이 사용할 수 없음)
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1", "LPT2",
+    "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    // 입력 파일명 (확장자 제외)
+    Stem,
+    // 저장 파일 확장자 (점 제외, 형식을 바꾸지 않았으면 입력 확장자)
+    Ext,
+    // 작업 순서 (1부터), width 만큼 0 으로 채움
+    Index { width: usize },
+    // 처리한 날짜 (chrono strftime 형식)
+    Date { format: String },
+    // 이미지에 넣은 텍스트
+    Text,
+    Width,
+    Height,
+}
+
+// 검증된 템플릿 (잘못된 항목은 역직렬화할 때 오류)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileNameTemplate {
+    tokens: Vec<Token>,
+}
+
+impl<'de> serde::Deserialize<'de> for FileNameTemplate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let template = String::deserialize(deserializer)?;
+        FileNameTemplate::parse(&template).map_err(serde::de::Error::custom)
+    }
+}
+
+// 템플릿에 채울 값
+pub struct FileNameContext<'a> {
+    pub image_path: &'a str,
+    pub text: &'a str,
+    // 작업 목록에서의 위치 (0부터, {index} 에는 1을 더해서 넣음)
+    pub index: usize,
+    // 디코딩한 뒤에만 알 수 있음
+    pub dimensions: Option<(u32, u32)>,
+    // 저장 형식을 바꿨을 때의 확장자 ({ext} 에 입력 확장자 대신 넣음)
+    pub output_extension: Option<&'a str>,
+    // 출력 폴더 아래 하위 폴더 (검증된 상대 경로)
+    pub subfolder: Option<&'a str>,
+}
+
+impl FileNameTemplate {
+    // "{{" / "}}" 는 중괄호 문자 그대로
+    pub fn parse(template: &str) -> Result<Self, String> {
+        if template.trim().is_empty() {
+            return Err("파일명 템플릿이 비어있습니다.".to_string());
+        }
+
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(ch) => placeholder.push(ch),
+                            None => return Err(format!("파일명 템플릿의 중괄호가 닫히지 않았습니다: {}", template)),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(parse_placeholder(&placeholder)?);
+                }
+                '}' => return Err(format!("파일명 템플릿에 짝이 없는 '}}' 가 있습니다: {}", template)),
+                ch => literal.push(ch),
+            }
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+
+        Ok(FileNameTemplate { tokens })
+    }
+
+    // {width} / {height} 가 있으면 이미지를 디코딩해야 파일명을 정할 수 있음
+    pub fn needs_dimensions(&self) -> bool {
+        self.tokens.iter().any(|token| matches!(token, Token::Width | Token::Height))
+    }
+
+    // 파일 시스템에서 쓸 수 있는 파일명으로 만듦
+    pub fn render(&self, context: &FileNameContext) -> String {
+        let path = Path::new(context.image_path);
+        let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+        let ext = match context.output_extension {
+            Some(extension) => extension.into(),
+            None => path.extension().map(|ext| ext.to_string_lossy()).unwrap_or_default(),
+        };
+        let (width, height) = context.dimensions.unwrap_or((0, 0));
+
+        let mut name = String::new();
+        for token in &self.tokens {
+            match token {
+                Token::Literal(text) => name.push_str(text),
+                Token::Stem => name.push_str(&stem),
+                Token::Ext => name.push_str(&ext),
+                Token::Index { width } => name.push_str(&format!("{:0width$}", context.index + 1, width = *width)),
+                Token::Date { format } => name.push_str(&chrono::Local::now().format(format).to_string()),
+                Token::Text => name.extend(
+                    context
+                        .text
+                        .chars()
+                        .map(|ch| if ch.is_whitespace() { ' ' } else { ch })
+                        .take(MAX_TEXT_CHARS),
+                ),
+                Token::Width => name.push_str(&width.to_string()),
+                Token::Height => name.push_str(&height.to_string()),
+            }
+        }
+
+        // NFD 파일명({stem})과 NFC 텍스트({text})가 섞이지 않도록 NFC 로 통일
+        sanitize_file_name(&unicode::to_nfc(&name))
+    }
+}
+
+// "name" 또는 "name:spec"
+fn parse_placeholder(placeholder: &str) -> Result<Token, String> {
+    let (name, spec) = match placeholder.split_once(':') {
+        Some((name, spec)) => (name.trim(), Some(spec)),
+        None => (placeholder.trim(), None),
+    };
+
+    let token = match name {
+        "stem" => Token::Stem,
+        "ext" => Token::Ext,
+        "text" => Token::Text,
+        "width" => Token::Width,
+        "height" => Token::Height,
+        "index" => {
+            // {index:04} → 4자리, 0으로 채움
+            let width = match spec {
+                Some(spec) => spec
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&width| width <= 10)
+                    .ok_or_else(|| format!("index 자릿수가 유효하지 않습니다: {}", spec))?,
+                None => 0,
+            };
+            return Ok(Token::Index { width });
+        }
+        "date" => {
+            let format = spec.unwrap_or(DEFAULT_DATE_FORMAT);
+            if format.is_empty() || StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(format!("날짜 형식이 유효하지 않습니다: {}", format));
+            }
+            return Ok(Token::Date { format: format.to_string() });
+        }
+        _ => return Err(format!("알 수 없는 파일명 항목입니다: {{{}}}", placeholder)),
+    };
+
+    if spec.is_some() {
+        return Err(format!("{{{}}} 에는 형식을 지정할 수 없습니다.", name));
+    }
+    Ok(token)
+}
+
+// 금지 문자/제어 문자는 '_' 로, 끝의 점과 공백은 제거, 예약 이름은 앞에 '_' 추가, 너무 길면 확장자를 남기고 자름
+pub fn sanitize_file_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|ch| match ch {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            ch if ch.is_control() => '_',
+            ch => ch,
+        })
+        .collect();
+
+    let mut name = replaced.trim().trim_end_matches(['.', ' ']).to_string();
+    if name.is_empty() {
+        name = "image".to_string();
+    }
+
+    let base = name.split('.').next().unwrap_or_default().trim_end();
+    if RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(base)) {
+        name.insert(0, '_');
+    }
+
+    truncate_file_name(name)
+}
+
+fn truncate_file_name(name: String) -> String {
+    if name.len() <= MAX_FILE_NAME_BYTES {
+        return name;
+    }
+
+    // 짧은 확장자는 남김 (".jpg" 등)
+    let (stem, ext) = match name.rfind('.') {
+        Some(dot) if dot > 0 && name.len() - dot <= 16 => (&name[..dot], &name[dot..]),
+        _ => (name.as_str(), ""),
+    };
+    let mut end = MAX_FILE_NAME_BYTES - ext.len();
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", stem[..end].trim_end_matches(['.', ' ']), ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(image_path: &'a str, text: &'a str) -> FileNameContext<'a> {
+        FileNameContext {
+            image_path,
+            text,
+            index: 6,
+            dimensions: Some((1920, 1080)),
+            output_extension: None,
+            subfolder: None,
+        }
+    }
+
+    fn render(template: &str, context: &FileNameContext) -> String {
+        FileNameTemplate::parse(template).unwrap().render(context)
+    }
+
+    #[test]
+    fn parse_splits_placeholders_and_literals() {
+        let template = FileNameTemplate::parse("{stem}_{index:04}.{ext}").unwrap();
+        assert_eq!(
+            template.tokens,
+            [
+                Token::Stem,
+                Token::Literal("_".to_string()),
+                Token::Index { width: 4 },
+                Token::Literal(".".to_string()),
+                Token::Ext,
+            ]
+        );
+        assert!(!template.needs_dimensions());
+        assert!(FileNameTemplate::parse("{width}x{height}").unwrap().needs_dimensions());
+        assert_eq!(
+            FileNameTemplate::parse("{{ {date:%Y} }}").unwrap().tokens,
+            [
+                Token::Literal("{ ".to_string()),
+                Token::Date { format: "%Y".to_string() },
+                Token::Literal(" }".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_templates() {
+        for template in ["", "  ", "{stem", "stem}", "{name}", "{index:x}", "{index:11}", "{stem:04}", "{date:%Q}", "{date:}"] {
+            assert!(FileNameTemplate::parse(template).is_err(), "{:?}", template);
+        }
+    }
+
+    #[test]
+    fn render_fills_values() {
+        let ctx = context("/photos/IMG_01.jpeg", "서울  여행");
+        assert_eq!(render("{stem}_labeled.{ext}", &ctx), "IMG_01_labeled.jpeg");
+        assert_eq!(render("{index:03}_{width}x{height}.{ext}", &ctx), "007_1920x1080.jpeg");
+        assert_eq!(render("{index}-{text}.{ext}", &ctx), "7-서울  여행.jpeg");
+        let converted = FileNameContext { output_extension: Some("webp"), ..context("/photos/IMG_01.jpeg", "") };
+        assert_eq!(render("{stem}.{ext}", &converted), "IMG_01.webp");
+    }
+
+    #[test]
+    fn render_normalizes_nfd_stem_to_nfc() {
+        let nfd: String = unicode_normalization::UnicodeNormalization::nfd("한글").collect();
+        let path = format!("/photos/{}.png", nfd);
+        assert_eq!(render("{stem}_{text}.{ext}", &context(&path, "한글")), "한글_한글.png");
+    }
+
+    #[test]
+    fn sanitize_replaces_forbidden_characters() {
+        assert_eq!(sanitize_file_name("a/b\\c:d*e?f\"g<h>i|j"), "a_b_c_d_e_f_g_h_i_j");
+        assert_eq!(sanitize_file_name("line\nbreak\t.jpg"), "line_break_.jpg");
+        assert_eq!(sanitize_file_name("  name. . "), "name");
+        assert_eq!(sanitize_file_name(" .. "), "image");
+    }
+
+    #[test]
+    fn sanitize_escapes_reserved_names() {
+        assert_eq!(sanitize_file_name("CON"), "_CON");
+        assert_eq!(sanitize_file_name("nul.txt"), "_nul.txt");
+        assert_eq!(sanitize_file_name("com1 .jpg"), "_com1 .jpg");
+        assert_eq!(sanitize_file_name("CONSOLE.jpg"), "CONSOLE.jpg");
+    }
+
+    #[test]
+    fn sanitize_truncates_long_names_keeping_extension() {
+        let name = sanitize_file_name(&format!("{}.jpg", "가".repeat(100)));
+        assert!(name.len() <= MAX_FILE_NAME_BYTES);
+        assert!(name.ends_with("가.jpg"));
+
+        let name = sanitize_file_name(&"a".repeat(300));
+        assert_eq!(name.len(), MAX_FILE_NAME_BYTES);
+    }
+}
@@ -0,0 +1,712 @@
+// 폰트 캐시 (Tauri State 로 공유)
+//
+// 폰트 파일은 수 MB 의 .ttc 인 경우가 많아서 이미지마다 다시 읽지 않고
+// 처음 요청될 때 한 번만 읽어 파싱한 뒤 재사용합니다.
+
+use rusttype::{Font, Scale};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use ttf_parser::name_id;
+
+use crate::unicode;
+
+// 개선된 한글 지원 폰트 경로
+pub const FONT_PATHS: &[&str] = &[
+    // macOS 한글 폰트 (우선순위 높음)
+    "/System/Library/Fonts/Supplemental/AppleSDGothicNeo.ttc",
+    "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+    "/Library/Fonts/AppleSDGothicNeo.ttc",
+    "/System/Library/Fonts/AppleGothic.ttf",
+    "/Library/Fonts/AppleGothic.ttf",
+
+    // macOS 기본 한글 지원 폰트
+    "/System/Library/Fonts/Helvetica.ttc",
+    "/System/Library/Fonts/ArialUnicodeMS.ttf",
+    "/System/Library/Fonts/PingFang.ttc",
+
+    // Windows 한글 폰트
+    "C:/Windows/Fonts/malgun.ttf",     // 맑은 고딕
+    "C:/Windows/Fonts/batang.ttc",     // 바탕
+    "C:/Windows/Fonts/gulim.ttc",      // 굴림
+
+    // Linux 한글 폰트
+    "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
+    "/usr/share/fonts/truetype/nanum/NanumBarunGothic.ttf",
+    "/usr/share/fonts/truetype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+
+    // 기본 폰트들
+    "/System/Library/Fonts/Arial.ttf",
+    "/Library/Fonts/Arial.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+];
+
+// 기본 폰트에 없는 글자(한자, 가나, 기호, 이모지 등)를 그릴 때 차례로 찾아보는 폰트
+// 컬러 비트맵 이모지 폰트(Apple Color Emoji 등)는 외곽선이 없어 그릴 수 없으므로 제외
+const FALLBACK_FONT_PATHS: &[&str] = &[
+    // macOS
+    "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+    "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "/System/Library/Fonts/Apple Symbols.ttf",
+
+    // Windows
+    "C:/Windows/Fonts/malgun.ttf",
+    "C:/Windows/Fonts/msgothic.ttc",
+    "C:/Windows/Fonts/msyh.ttc",
+    "C:/Windows/Fonts/seguisym.ttf",
+    "C:/Windows/Fonts/seguiemj.ttf",
+
+    // Linux
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/noto/NotoSansSymbols2-Regular.ttf",
+    "/usr/share/fonts/truetype/noto/NotoEmoji-Regular.ttf",
+    "/usr/share/fonts/truetype/ancient-scripts/Symbola_hint.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+];
+
+// 폰트 파일 확장자 (.ttc/.otc 는 여러 폰트를 담은 컬렉션)
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc", "otc"];
+
+// 폰트 폴더 탐색 깊이 (Linux 는 /usr/share/fonts/truetype/<family>/ 처럼 중첩됨)
+const MAX_SCAN_DEPTH: usize = 5;
+
+// 폰트 이름 테이블의 Windows 언어 ID
+const ENGLISH: u16 = 0x0409;
+const KOREAN: u16 = 0x0412;
+
+// 폰트 목록을 만들 때 읽는 컬렉션 안 폰트 수 상한 (손상된 헤더로 큰 메모리를 잡지 않도록)
+const MAX_COLLECTION_FACES: u32 = 1024;
+
+// 바이너리에 내장하는 최종 대체 폰트 (SIL OFL 1.1, fonts/OFL.txt 참고)
+// 설치된 폰트가 하나도 없는 환경에서도 색상 블록 대신 실제 글자를 그리기 위해 사용
+#[cfg(feature = "bundled-font")]
+const BUNDLED_FONT: &[u8] = include_bytes!("../fonts/NanumGothic-Regular.ttf");
+#[cfg(feature = "bundled-font")]
+const BUNDLED_FONT_NAME: &str = "NanumGothic Regular (내장 폰트)";
+
+// 파싱된 폰트와 실제로 어떤 폰트인지 알려주는 이름
+pub struct LoadedFont {
+    pub font: Font<'static>,
+    // 예: "AppleSDGothicNeo Regular (/System/Library/Fonts/AppleSDGothicNeo.ttc#0)"
+    pub name: String,
+    // 컬렉션(.ttc) 안의 위치 (셰이핑할 때 같은 폰트를 다시 찾음)
+    pub index: u32,
+}
+
+impl LoadedFont {
+    // OpenType 셰이핑용 폰트 (rusttype 가 들고 있는 파일 내용을 그대로 사용, 읽을 수 없으면 None)
+    pub fn shaping_face(&self) -> Option<rustybuzz::Face<'_>> {
+        match &self.font {
+            Font::Owned(face) => rustybuzz::Face::from_slice(face.as_slice(), self.index),
+            Font::Ref(_) => None,
+        }
+    }
+}
+
+impl std::ops::Deref for LoadedFont {
+    type Target = Font<'static>;
+
+    fn deref(&self) -> &Font<'static> {
+        &self.font
+    }
+}
+
+pub type SharedFont = Arc<LoadedFont>;
+
+// 글자마다 글리프가 있는 첫 번째 폰트를 사용하는 대체 폰트 목록
+// (선택한 폰트 → 기본 폰트 → FALLBACK_FONT_PATHS → 내장 폰트 순)
+#[derive(Clone, Default)]
+pub struct FontChain {
+    fonts: Vec<SharedFont>,
+}
+
+impl FontChain {
+    pub fn new(fonts: Vec<SharedFont>) -> Self {
+        let mut chain = FontChain { fonts: Vec::new() };
+        for font in fonts {
+            if !chain.fonts.iter().any(|existing| existing.name == font.name) {
+                chain.fonts.push(font);
+            }
+        }
+        chain
+    }
+
+    // 선택한 폰트 (줄 높이/ascent 기준)
+    pub fn primary(&self) -> Option<&LoadedFont> {
+        self.fonts.first().map(|font| font.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    pub fn get(&self, index: usize) -> &LoadedFont {
+        &self.fonts[index]
+    }
+
+    // 글자를 그릴 폰트의 인덱스 (어느 폰트에도 없으면 선택한 폰트)
+    pub fn font_index_for(&self, ch: char) -> usize {
+        self.fonts
+            .iter()
+            .position(|font| has_glyph(font, ch))
+            .unwrap_or(0)
+    }
+
+    // 텍스트를 그리는 데 실제로 쓰이는 폰트 이름 (결과 보고용)
+    pub fn names_used(&self, text: &str) -> Vec<String> {
+        let mut used = vec![false; self.fonts.len()];
+        // 그릴 때와 같이 NFC 로 합친 글자 기준
+        for ch in unicode::to_nfc(text).chars().filter(|ch| !ch.is_whitespace() && !is_invisible(*ch)) {
+            if let Some(flag) = used.get_mut(self.font_index_for(ch)) {
+                *flag = true;
+            }
+        }
+
+        self.fonts
+            .iter()
+            .zip(used)
+            .filter(|(_, used)| *used)
+            .map(|(font, _)| font.name.clone())
+            .collect()
+    }
+}
+
+// 폰트에 그릴 수 있는 글리프가 있는지 (외곽선이 없는 비트맵 전용 글리프는 제외)
+fn has_glyph(font: &Font, ch: char) -> bool {
+    let glyph = font.glyph(ch);
+    glyph.id().0 != 0
+        && (ch.is_whitespace() || glyph.scaled(Scale::uniform(32.0)).exact_bounding_box().is_some())
+}
+
+// 폭이 없는 서식 문자 (ZWJ, 이모지 변형 선택자 등)는 그리지 않음
+pub fn is_invisible(ch: char) -> bool {
+    ch.is_control()
+        || matches!(ch, '\u{200B}'..='\u{200F}' | '\u{2060}' | '\u{FE00}'..='\u{FE0F}' | '\u{FEFF}')
+}
+
+// list_fonts 결과 항목 (컬렉션의 경우 폰트 하나당 한 항목)
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FontInfo {
+    // "<경로>#<컬렉션 인덱스>" 형식의 폰트 식별자
+    pub id: String,
+    pub family: String,
+    pub style: String,
+    // 한국어 패밀리 이름 (예: "나눔명조")
+    pub localized_family: Option<String>,
+    pub path: String,
+    pub index: u32,
+}
+
+#[derive(Default)]
+pub struct FontRegistry {
+    // 폰트 식별자별로 파싱된 폰트
+    fonts: Mutex<HashMap<String, SharedFont>>,
+    // 기본 폰트 (FONT_PATHS 에서 처음으로 읽히는 폰트, 한 번만 탐색)
+    default_font: OnceLock<Option<SharedFont>>,
+    // 대체 폰트 (최초 사용 시 한 번만 로드)
+    fallback_fonts: OnceLock<Vec<SharedFont>>,
+    // 시스템 폰트 목록 (최초 list_fonts 시 한 번만 스캔)
+    system_fonts: OnceLock<Vec<FontInfo>>,
+    // 사용자가 추가한 폰트 폴더 (폴더를 다시 지정할 때까지 스캔 결과 재사용)
+    user_font_dir: Mutex<UserFontDir>,
+}
+
+#[derive(Default)]
+struct UserFontDir {
+    dir: Option<PathBuf>,
+    // 최초 list_fonts 시 스캔 (None 이면 아직 스캔하지 않음)
+    fonts: Option<Vec<FontInfo>>,
+}
+
+impl FontRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 기본 한글 지원 폰트 (최초 호출 시에만 파일을 읽음)
+    // 설치된 폰트가 없으면 내장 폰트 사용
+    pub fn default_font(&self) -> Option<SharedFont> {
+        self.default_font
+            .get_or_init(|| {
+                let font = FONT_PATHS
+                    .iter()
+                    .find_map(|font_path| self.load(font_path))
+                    .or_else(bundled_font);
+
+                match &font {
+                    Some(font) => eprintln!("기본 폰트: {}", font.name),
+                    None => eprintln!("경고: 사용 가능한 폰트가 없습니다."),
+                }
+                font
+            })
+            .clone()
+    }
+
+    // 작업에 사용할 대체 폰트 목록 (선택한 폰트를 찾을 수 없으면 에러)
+    pub fn font_chain(&self, font: Option<&str>) -> Result<FontChain, String> {
+        let mut fonts: Vec<SharedFont> = Vec::new();
+        fonts.extend(self.resolve(font)?);
+        fonts.extend(self.default_font());
+        fonts.extend(self.fallback_fonts().iter().cloned());
+        Ok(FontChain::new(fonts))
+    }
+
+    fn fallback_fonts(&self) -> &[SharedFont] {
+        self.fallback_fonts.get_or_init(|| {
+            FALLBACK_FONT_PATHS
+                .iter()
+                .filter_map(|font_path| self.load(font_path))
+                .chain(bundled_font())
+                .collect()
+        })
+    }
+
+    // 경로로 폰트 로드 (이미 읽은 폰트는 캐시에서 반환)
+    pub fn load(&self, font_path: &str) -> Option<SharedFont> {
+        self.load_face(font_path, 0)
+    }
+
+    // 컬렉션(.ttc) 안의 특정 폰트 로드
+    pub fn load_face(&self, font_path: &str, index: u32) -> Option<SharedFont> {
+        let key = font_id(font_path, index);
+        if let Some(font) = self.lock().get(&key) {
+            return Some(font.clone());
+        }
+
+        let font_data = fs::read(font_path).ok()?;
+        let name = match face_display_name(&font_data, index) {
+            Some(face_name) => format!("{} ({})", face_name, key),
+            None => key.clone(),
+        };
+        let font = Arc::new(LoadedFont {
+            font: Font::try_from_vec_and_index(font_data, index)?,
+            name,
+            index,
+        });
+
+        let mut fonts = self.lock();
+        Some(fonts.entry(key).or_insert(font).clone())
+    }
+
+    // 작업에 사용할 폰트 결정
+    // 지정하지 않으면 기본 폰트, 지정했는데 찾을 수 없으면 에러 (다른 폰트로 몰래 바꾸지 않음)
+    pub fn resolve(&self, font: Option<&str>) -> Result<Option<SharedFont>, String> {
+        let font = match font.map(str::trim) {
+            Some(font) if !font.is_empty() => font,
+            _ => return Ok(self.default_font()),
+        };
+
+        // 1) "<경로>#<인덱스>" 또는 파일 경로
+        let (font_path, index) = parse_font_id(font);
+        if Path::new(font_path).is_file() {
+            return self
+                .load_face(font_path, index)
+                .map(Some)
+                .ok_or_else(|| format!("폰트 파일을 읽을 수 없습니다: {}", font));
+        }
+
+        // 2) 패밀리 이름 (여러 스타일이 있으면 Regular 우선)
+        let candidates: Vec<FontInfo> = self
+            .list_fonts()
+            .into_iter()
+            .filter(|info| {
+                info.family.eq_ignore_ascii_case(font)
+                    || info.localized_family.as_deref() == Some(font)
+            })
+            .collect();
+
+        let info = candidates
+            .iter()
+            .find(|info| is_regular_style(&info.style))
+            .or_else(|| candidates.first())
+            .ok_or_else(|| format!("폰트를 찾을 수 없습니다: {}", font))?;
+
+        self.load_face(&info.path, info.index)
+            .map(Some)
+            .ok_or_else(|| format!("폰트 파일을 읽을 수 없습니다: {}", info.path))
+    }
+
+    // 시스템 폰트 + 사용자 폰트 폴더 목록
+    pub fn list_fonts(&self) -> Vec<FontInfo> {
+        let mut fonts = self
+            .system_fonts
+            .get_or_init(|| {
+                let mut fonts = Vec::new();
+                for dir in platform_font_dirs() {
+                    scan_font_dir(&dir, 0, &mut fonts);
+                }
+                fonts
+            })
+            .clone();
+
+        fonts.extend(self.user_fonts());
+
+        fonts.sort_by(|a, b| a.family.cmp(&b.family).then_with(|| a.style.cmp(&b.style)));
+        fonts.dedup_by(|a, b| a.id == b.id);
+        fonts
+    }
+
+    // 같은 폴더를 다시 지정해도 새로 추가한 폰트가 보이도록 스캔 결과를 버림
+    pub fn set_user_font_dir(&self, dir: Option<PathBuf>) {
+        *self.user_font_dir.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = UserFontDir { dir, fonts: None };
+    }
+
+    // 폰트 이름으로 찾을 때마다 폴더의 폰트 파일을 전부 다시 읽지 않도록 한 번만 스캔
+    fn user_fonts(&self) -> Vec<FontInfo> {
+        let mut user = self.user_font_dir.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let UserFontDir { dir, fonts } = &mut *user;
+        let Some(dir) = dir else {
+            return Vec::new();
+        };
+        fonts
+            .get_or_insert_with(|| {
+                let mut fonts = Vec::new();
+                scan_font_dir(dir, 0, &mut fonts);
+                fonts
+            })
+            .clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, SharedFont>> {
+        // 다른 스레드가 패닉해도 캐시 자체는 유효하므로 계속 사용
+        self.fonts.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// 셰이핑에 파일 내용이 필요하므로 다른 폰트처럼 소유한 데이터로 읽음
+#[cfg(feature = "bundled-font")]
+fn bundled_font() -> Option<SharedFont> {
+    Font::try_from_vec(BUNDLED_FONT.to_vec()).map(|font| {
+        Arc::new(LoadedFont {
+            font,
+            name: BUNDLED_FONT_NAME.to_string(),
+            index: 0,
+        })
+    })
+}
+
+#[cfg(not(feature = "bundled-font"))]
+fn bundled_font() -> Option<SharedFont> {
+    None
+}
+
+pub fn font_id(font_path: &str, index: u32) -> String {
+    format!("{}#{}", font_path, index)
+}
+
+// "<경로>#<인덱스>" 분리 (인덱스가 없으면 0)
+fn parse_font_id(font: &str) -> (&str, u32) {
+    match font.rsplit_once('#') {
+        Some((font_path, index)) => match index.parse() {
+            Ok(index) => (font_path, index),
+            Err(_) => (font, 0),
+        },
+        None => (font, 0),
+    }
+}
+
+fn is_regular_style(style: &str) -> bool {
+    matches!(style.to_lowercase().as_str(), "regular" | "normal" | "book" | "roman" | "medium")
+}
+
+// 운영체제별 기본 폰트 폴더
+fn platform_font_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut dirs = Vec::new();
+
+    if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        if let Some(home) = &home {
+            dirs.push(home.join("Library/Fonts"));
+        }
+    } else if cfg!(target_os = "windows") {
+        let windir = std::env::var_os("WINDIR").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("C:/Windows"));
+        dirs.push(windir.join("Fonts"));
+        if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+            dirs.push(PathBuf::from(local).join("Microsoft/Windows/Fonts"));
+        }
+    } else {
+        dirs.push(PathBuf::from("/usr/share/fonts"));
+        dirs.push(PathBuf::from("/usr/local/share/fonts"));
+        if let Some(home) = &home {
+            dirs.push(home.join(".fonts"));
+            dirs.push(home.join(".local/share/fonts"));
+        }
+    }
+
+    dirs
+}
+
+fn scan_font_dir(dir: &Path, depth: usize, fonts: &mut Vec<FontInfo>) {
+    if depth > MAX_SCAN_DEPTH {
+        return;
+    }
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            scan_font_dir(&path, depth + 1, fonts);
+            continue;
+        }
+
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+        if FONT_EXTENSIONS.contains(&extension.as_str()) {
+            read_font_faces(&path, fonts);
+        }
+    }
+}
+
+// 폰트 파일 하나에 들어 있는 모든 폰트의 이름 읽기
+// 수 MB 의 파일 전체를 읽고 파싱하지 않도록 폰트마다 name 테이블만 읽음
+fn read_font_faces(path: &Path, fonts: &mut Vec<FontInfo>) {
+    let mut file = match FontFile::open(path) {
+        Some(file) => file,
+        None => return,
+    };
+    let font_path = path.to_string_lossy().to_string();
+
+    for (index, name_table) in file.name_tables().into_iter().enumerate() {
+        let names = match name_table.as_deref().and_then(ttf_parser::name::Table::parse) {
+            Some(table) => table.names,
+            None => continue,
+        };
+
+        let (family, style) = match face_family_and_style(names) {
+            Some(names) => names,
+            None => continue,
+        };
+        let localized_family = face_name(names, name_id::TYPOGRAPHIC_FAMILY, KOREAN)
+            .or_else(|| face_name(names, name_id::FAMILY, KOREAN))
+            .filter(|localized| *localized != family);
+
+        let index = index as u32;
+        fonts.push(FontInfo {
+            id: font_id(&font_path, index),
+            family,
+            style,
+            localized_family,
+            path: font_path.clone(),
+            index,
+        });
+    }
+}
+
+// 필요한 부분만 골라 읽는 폰트 파일 (빅 엔디언 OpenType 구조)
+struct FontFile {
+    file: File,
+    len: u64,
+}
+
+impl FontFile {
+    fn open(path: &Path) -> Option<Self> {
+        let file = File::open(path).ok()?;
+        let len = file.metadata().ok()?.len();
+        Some(FontFile { file, len })
+    }
+
+    // 폰트마다 name 테이블 내용 (컬렉션이면 들어 있는 순서대로, 찾을 수 없으면 None)
+    fn name_tables(&mut self) -> Vec<Option<Vec<u8>>> {
+        let header = match self.read(0, 12) {
+            Some(header) => header,
+            None => return Vec::new(),
+        };
+        if &header[..4] != b"ttcf" {
+            return vec![self.name_table(0)];
+        }
+
+        // 컬렉션 헤더: "ttcf", 버전(4), 폰트 수(4), 폰트마다 오프셋 테이블 위치(4)
+        let count = be_u32(&header, 8).min(MAX_COLLECTION_FACES);
+        let offsets = self.read(12, count as usize * 4).unwrap_or_default();
+        offsets
+            .chunks_exact(4)
+            .map(|offset| self.name_table(be_u32(offset, 0) as u64))
+            .collect()
+    }
+
+    // 오프셋 테이블 (12바이트) 뒤의 테이블 목록에서 name 을 찾아 읽음
+    // 목록 항목은 16바이트: 태그(4), 체크섬(4), 위치(4), 길이(4)
+    fn name_table(&mut self, offset: u64) -> Option<Vec<u8>> {
+        let header = self.read(offset, 12)?;
+        let table_count = u16::from_be_bytes([header[4], header[5]]) as usize;
+        let records = self.read(offset + 12, table_count * 16)?;
+        let record = records.chunks_exact(16).find(|record| &record[..4] == b"name")?;
+        self.read(be_u32(record, 8) as u64, be_u32(record, 12) as usize)
+    }
+
+    // 파일 범위를 벗어나면 None
+    fn read(&mut self, offset: u64, length: usize) -> Option<Vec<u8>> {
+        if offset.checked_add(length as u64)? > self.len {
+            return None;
+        }
+        self.file.seek(SeekFrom::Start(offset)).ok()?;
+        let mut data = vec![0; length];
+        self.file.read_exact(&mut data).ok()?;
+        Some(data)
+    }
+}
+
+fn be_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+}
+
+fn face_family_and_style(names: ttf_parser::name::Names) -> Option<(String, String)> {
+    let family = face_name(names, name_id::TYPOGRAPHIC_FAMILY, ENGLISH)
+        .or_else(|| face_name(names, name_id::FAMILY, ENGLISH))?;
+    let style = face_name(names, name_id::TYPOGRAPHIC_SUBFAMILY, ENGLISH)
+        .or_else(|| face_name(names, name_id::SUBFAMILY, ENGLISH))
+        .unwrap_or_else(|| "Regular".to_string());
+    Some((family, style))
+}
+
+// 로그/결과 표시용 "패밀리 스타일" 이름
+fn face_display_name(font_data: &[u8], index: u32) -> Option<String> {
+    let face = ttf_parser::Face::from_slice(font_data, index).ok()?;
+    let (family, style) = face_family_and_style(face.names())?;
+    Some(format!("{} {}", family, style))
+}
+
+// 지정한 언어의 이름, 영어 이름이 없으면 첫 번째 유니코드 이름
+fn face_name(names: ttf_parser::name::Names, id: u16, language_id: u16) -> Option<String> {
+    let names: Vec<_> = names
+        .into_iter()
+        .filter(|name| name.name_id == id && name.is_unicode())
+        .collect();
+
+    names
+        .iter()
+        .find(|name| name.language_id == language_id)
+        .or_else(|| if language_id == ENGLISH { names.first() } else { None })
+        .and_then(|name| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (이름 ID, 언어 ID, 이름) 을 담은 name 테이블 (형식 0, Windows 유니코드 = UTF-16BE)
+    fn name_table(names: &[(u16, u16, &str)]) -> Vec<u8> {
+        let mut records = Vec::new();
+        let mut storage = Vec::new();
+        for &(name_id, language_id, name) in names {
+            let encoded: Vec<u8> = name.encode_utf16().flat_map(u16::to_be_bytes).collect();
+            for value in [3, 1, language_id, name_id, encoded.len() as u16, storage.len() as u16] {
+                records.extend_from_slice(&value.to_be_bytes());
+            }
+            storage.extend_from_slice(&encoded);
+        }
+        let mut table = Vec::new();
+        for value in [0, names.len() as u16, 6 + records.len() as u16] {
+            table.extend_from_slice(&value.to_be_bytes());
+        }
+        table.extend_from_slice(&records);
+        table.extend_from_slice(&storage);
+        table
+    }
+
+    // 파일의 start 위치에 놓일 폰트 하나 (오프셋 테이블 + 테이블 목록 + name 테이블)
+    fn font_at(start: u32, names: &[(u16, u16, &str)]) -> Vec<u8> {
+        let table = name_table(names);
+        let mut font = vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        font.extend_from_slice(b"name");
+        font.extend_from_slice(&0u32.to_be_bytes());
+        font.extend_from_slice(&(start + 28).to_be_bytes());
+        font.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        font.extend_from_slice(&table);
+        font
+    }
+
+    fn scan(name: &str, data: &[u8]) -> Vec<FontInfo> {
+        let path = std::env::temp_dir().join(format!("image-overlay-fonts-{}-{}", std::process::id(), name));
+        fs::write(&path, data).unwrap();
+        let mut fonts = Vec::new();
+        read_font_faces(&path, &mut fonts);
+        fs::remove_file(&path).unwrap();
+        fonts
+    }
+
+    #[test]
+    fn reads_names_from_name_table() {
+        let font = font_at(0, &[
+            (name_id::FAMILY, ENGLISH, "Nanum Myeongjo"),
+            (name_id::SUBFAMILY, ENGLISH, "Bold"),
+            (name_id::FAMILY, KOREAN, "나눔명조"),
+        ]);
+        let fonts = scan("single.ttf", &font);
+        assert_eq!(fonts.len(), 1);
+        assert_eq!((fonts[0].family.as_str(), fonts[0].style.as_str()), ("Nanum Myeongjo", "Bold"));
+        assert_eq!(fonts[0].localized_family.as_deref(), Some("나눔명조"));
+        assert_eq!(fonts[0].index, 0);
+        assert!(fonts[0].id.ends_with("single.ttf#0"));
+    }
+
+    #[test]
+    fn reads_every_face_in_collection() {
+        let first = font_at(20, &[(name_id::FAMILY, ENGLISH, "First")]);
+        let second_start = 20 + first.len() as u32;
+        let second = font_at(second_start, &[(name_id::TYPOGRAPHIC_FAMILY, ENGLISH, "Second"), (name_id::FAMILY, ENGLISH, "Second Light")]);
+
+        let mut collection = b"ttcf".to_vec();
+        for value in [0x0001_0000, 2, 20, second_start] {
+            collection.extend_from_slice(&u32::to_be_bytes(value));
+        }
+        collection.extend_from_slice(&first);
+        collection.extend_from_slice(&second);
+
+        let fonts = scan("collection.ttc", &collection);
+        let names: Vec<(&str, &str, u32)> = fonts.iter().map(|font| (font.family.as_str(), font.style.as_str(), font.index)).collect();
+        assert_eq!(names, vec![("First", "Regular", 0), ("Second", "Regular", 1)]);
+    }
+
+    #[test]
+    fn skips_broken_fonts() {
+        let font = font_at(0, &[(name_id::FAMILY, ENGLISH, "Broken")]);
+        // name 테이블이 파일 끝을 넘어감
+        assert!(scan("truncated.ttf", &font[..font.len() - 4]).is_empty());
+        // 이름 없는 폰트, 폰트가 아닌 파일
+        assert!(scan("nameless.ttf", &font_at(0, &[])).is_empty());
+        assert!(scan("text.ttf", b"not a font").is_empty());
+
+        // 폰트 수가 파일 크기를 넘는 컬렉션
+        let mut collection = b"ttcf".to_vec();
+        for value in [0x0001_0000, u32::MAX, 16] {
+            collection.extend_from_slice(&u32::to_be_bytes(value));
+        }
+        collection.extend_from_slice(&font_at(16, &[(name_id::FAMILY, ENGLISH, "Only")]));
+        assert!(scan("overflow.ttc", &collection).is_empty());
+    }
+
+    #[test]
+    fn matches_full_parse_for_installed_fonts() {
+        let mut installed = Vec::new();
+        for dir in platform_font_dirs() {
+            scan_font_dir(&dir, 0, &mut installed);
+        }
+        for info in installed.iter().take(50) {
+            let font_data = fs::read(&info.path).unwrap();
+            // 이름 외의 테이블이 손상된 폰트는 비교하지 않음
+            let Ok(face) = ttf_parser::Face::from_slice(&font_data, info.index) else { continue };
+            assert_eq!(face_family_and_style(face.names()), Some((info.family.clone(), info.style.clone())), "{}", info.id);
+        }
+    }
+}
@@ -0,0 +1,561 @@
+// 실제 글리프 메트릭 기반 텍스트 측정
+//
+// 글리프 배치는 OpenType 셰이핑 (rustybuzz, HarfBuzz 호환) 결과를 따르고
+// 아랍어/히브리어처럼 오른쪽에서 왼쪽으로 쓰는 구간은 유니코드 양방향 알고리즘으로 순서를 정합니다.
+// 좌표계는 imageproc 의 draw_text_mut 와 동일합니다.
+// 원점 (0, 0) = draw_text_mut 에 넘기는 (x, y), y = 0 은 ascent 상단입니다.
+
+use rustybuzz::Direction;
+use rusttype::{point, GlyphId, PositionedGlyph, Scale};
+use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
+use unicode_bidi::ParagraphBidiInfo;
+use unicode_normalization::char::is_combining_mark;
+
+use crate::fonts::{is_invisible, FontChain, LoadedFont};
+
+// 그리기 원점 기준 사각형 영역
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl TextBounds {
+    fn from_edges(min_x: i32, min_y: i32, max_x: i32, max_y: i32) -> Self {
+        TextBounds {
+            x: min_x,
+            y: min_y,
+            width: (max_x - min_x).max(1) as u32,
+            height: (max_y - min_y).max(1) as u32,
+        }
+    }
+
+    // 사방으로 margin 만큼 넓힌 영역 (배경 박스 패딩, 경계 여백)
+    pub fn expanded(&self, margin: i32) -> TextBounds {
+        TextBounds::from_edges(
+            self.x - margin,
+            self.y - margin,
+            self.x + self.width as i32 + margin,
+            self.y + self.height as i32 + margin,
+        )
+    }
+
+    // 가운데 (회전 중심)
+    pub fn center(&self) -> (f32, f32) {
+        (
+            self.x as f32 + self.width as f32 / 2.0,
+            self.y as f32 + self.height as f32 / 2.0,
+        )
+    }
+
+    fn union(&self, other: &TextBounds) -> TextBounds {
+        TextBounds::from_edges(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            (self.x + self.width as i32).max(other.x + other.width as i32),
+            (self.y + self.height as i32).max(other.y + other.height as i32),
+        )
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct TextMetrics {
+    // 커닝 포함 펜 이동 거리
+    pub advance_width: f32,
+    pub ascent: f32,
+    // baseline 아래쪽이므로 음수
+    pub descent: f32,
+    // 실제로 칠해지는 픽셀 영역 (공백만 있으면 None)
+    pub ink_bounds: Option<TextBounds>,
+    // 배경 박스 기준 영역: advance 박스와 잉크 영역의 합집합
+    pub bounds: TextBounds,
+    // 회전했을 때 bounds 를 가운데 기준으로 돌린 영역을 감싸는 사각형 (회전하지 않으면 None)
+    pub rotated_bounds: Option<TextBounds>,
+    // 줄바꿈 후 줄 수
+    pub line_count: usize,
+}
+
+impl TextMetrics {
+    // 폰트 없이 너비/높이만 알고 있을 때 (대체 렌더링용)
+    pub fn from_size(width: u32, height: u32) -> Self {
+        TextMetrics {
+            advance_width: width as f32,
+            ascent: height as f32,
+            descent: 0.0,
+            ink_bounds: None,
+            bounds: TextBounds::from_edges(0, 0, width as i32, height as i32),
+            rotated_bounds: None,
+            line_count: 1,
+        }
+    }
+}
+
+// 여러 줄 텍스트의 줄 정렬
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutOptions {
+    // 이 너비(px)를 넘으면 자동 줄바꿈 (None 이면 \n 에서만 줄바꿈)
+    pub max_width: Option<f32>,
+    pub align: TextAlign,
+    // 폰트 기본 줄 높이(ascent - descent + line_gap)에 곱하는 배율
+    pub line_height: f32,
+}
+
+impl LayoutOptions {
+    pub const DEFAULT: LayoutOptions = LayoutOptions {
+        max_width: None,
+        align: TextAlign::Left,
+        line_height: 1.0,
+    };
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        LayoutOptions::DEFAULT
+    }
+}
+
+// 배치된 글리프와 그 크기 (렌더링과 측정이 같은 배치를 쓰도록 함께 반환)
+pub struct TextLayout {
+    pub glyphs: Vec<PositionedGlyph<'static>>,
+    pub metrics: TextMetrics,
+}
+
+// 배치하는 동안 폰트별 셰이핑 정보를 한 번만 만들어 재사용
+struct Shaper<'a> {
+    fonts: &'a FontChain,
+    faces: Vec<OnceCell<Option<rustybuzz::Face<'a>>>>,
+}
+
+impl<'a> Shaper<'a> {
+    fn new(fonts: &'a FontChain) -> Self {
+        Shaper {
+            fonts,
+            faces: (0..fonts.len()).map(|_| OnceCell::new()).collect(),
+        }
+    }
+
+    fn face(&self, font_index: usize) -> Option<&rustybuzz::Face<'a>> {
+        let fonts = self.fonts;
+        self.faces[font_index]
+            .get_or_init(|| fonts.get(font_index).shaping_face())
+            .as_ref()
+    }
+}
+
+// 한 줄을 caret_x 부터 baseline 위에 배치하고 끝 위치(커닝 포함 advance)를 반환
+// 양방향 알고리즘으로 나눈 방향별 구간을 화면 순서대로 놓고, 구간 안에서는 폰트가 같은 부분끼리 셰이핑
+fn layout_line(shaper: &Shaper, line: &str, scale: Scale, caret_x: f32, baseline: f32, glyphs: &mut Vec<PositionedGlyph<'static>>) -> f32 {
+    let mut caret = caret_x;
+    if line.is_empty() {
+        return caret;
+    }
+
+    let bidi = ParagraphBidiInfo::new(line, None);
+    let (levels, runs) = bidi.visual_runs(0..line.len());
+    for run in runs {
+        let rtl = levels[run.start].is_rtl();
+        let mut font_runs = font_runs(shaper.fonts, &line[run]);
+        // 오른쪽에서 왼쪽으로 쓰는 구간은 뒤쪽 글자가 왼쪽에 옴
+        if rtl {
+            font_runs.reverse();
+        }
+        for (font_index, text) in font_runs {
+            caret = match shaper.face(font_index) {
+                Some(face) => shape_run(shaper.fonts.get(font_index), face, text, rtl, scale, caret, baseline, glyphs),
+                None => place_chars(shaper.fonts.get(font_index), text, scale, caret, baseline, glyphs),
+            };
+        }
+    }
+
+    caret
+}
+
+// 같은 폰트로 그릴 연속 구간 (글자마다 대체 폰트를 고르되 결합 문자와 서식 문자는 앞 글자의 폰트를 따라감)
+// 제어 문자는 그리지 않음
+fn font_runs<'t>(fonts: &FontChain, text: &'t str) -> Vec<(usize, &'t str)> {
+    let mut runs = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+
+    for (i, ch) in text.char_indices() {
+        if ch.is_control() {
+            if let Some((font_index, start)) = current.take() {
+                runs.push((font_index, &text[start..i]));
+            }
+            continue;
+        }
+        let font_index = match current {
+            Some((font_index, _)) if is_invisible(ch) || is_combining_mark(ch) => font_index,
+            _ => fonts.font_index_for(ch),
+        };
+        match current {
+            Some((current_index, _)) if current_index == font_index => {}
+            Some((current_index, start)) => {
+                runs.push((current_index, &text[start..i]));
+                current = Some((font_index, i));
+            }
+            None => current = Some((font_index, i)),
+        }
+    }
+    if let Some((font_index, start)) = current {
+        runs.push((font_index, &text[start..]));
+    }
+    runs
+}
+
+// HarfBuzz 와 같은 방식으로 셰이핑 (GPOS 커닝, 합자, 아랍 문자 연결형, 인도계 문자 재배열 등)
+#[allow(clippy::too_many_arguments)]
+fn shape_run(
+    font: &LoadedFont,
+    face: &rustybuzz::Face,
+    text: &str,
+    rtl: bool,
+    scale: Scale,
+    caret_x: f32,
+    baseline: f32,
+    glyphs: &mut Vec<PositionedGlyph<'static>>,
+) -> f32 {
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.set_direction(if rtl { Direction::RightToLeft } else { Direction::LeftToRight });
+    buffer.guess_segment_properties();
+    let shaped = rustybuzz::shape(face, &[], buffer);
+
+    // 폰트 단위 → 픽셀 (rusttype 와 같은 기준: ascent - descent 가 font_size)
+    let factor_x = font.scale_for_pixel_height(scale.x);
+    let factor_y = font.scale_for_pixel_height(scale.y);
+    let mut caret = caret_x;
+    for (info, position) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+        let x = caret + position.x_offset as f32 * factor_x;
+        let y = baseline - position.y_offset as f32 * factor_y;
+        glyphs.push(font.glyph(GlyphId(info.glyph_id as u16)).scaled(scale).positioned(point(x, y)));
+        caret += position.x_advance as f32 * factor_x;
+    }
+    caret
+}
+
+// 셰이핑할 수 없는 폰트는 글자별 advance + kern 테이블 커닝으로 배치
+fn place_chars(font: &LoadedFont, text: &str, scale: Scale, caret_x: f32, baseline: f32, glyphs: &mut Vec<PositionedGlyph<'static>>) -> f32 {
+    let mut caret = caret_x;
+    let mut last: Option<GlyphId> = None;
+
+    for ch in text.chars().filter(|ch| !is_invisible(*ch)) {
+        let glyph = font.glyph(ch).scaled(scale);
+        if let Some(last_id) = last {
+            caret += font.pair_kerning(scale, last_id, glyph.id());
+        }
+
+        let advance = glyph.h_metrics().advance_width;
+        last = Some(glyph.id());
+        glyphs.push(glyph.positioned(point(caret, baseline)));
+        caret += advance;
+    }
+
+    caret
+}
+
+fn line_width(shaper: &Shaper, line: &str, scale: Scale) -> f32 {
+    layout_line(shaper, line, scale, 0.0, 0.0, &mut Vec::new())
+}
+
+// 한글/한자/가나는 글자 사이 어디서든 줄바꿈 가능
+fn is_cjk(ch: char) -> bool {
+    matches!(ch,
+        '\u{AC00}'..='\u{D7A3}' // 한글 음절
+        | '\u{3040}'..='\u{30FF}' // 히라가나, 가타카나
+        | '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' // 한자
+    )
+}
+
+// 줄 앞에 오면 안 되는 문장부호
+fn is_closing_punctuation(ch: char) -> bool {
+    matches!(ch, ',' | '.' | '!' | '?' | ';' | ':' | ')' | ']' | '}' | '、' | '。' | '」' | '』' | '》' | '〉' | '…' | '~')
+}
+
+// 줄바꿈 가능한 위치로 문단을 나눔 (라틴 문자는 공백 뒤, 한글/CJK 는 글자 사이)
+fn break_segments(paragraph: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut chars = paragraph.char_indices().peekable();
+
+    while let Some((i, ch)) = chars.next() {
+        let end = i + ch.len_utf8();
+        let next = match chars.peek() {
+            Some(&(_, next)) => next,
+            None => break,
+        };
+
+        let can_break = !next.is_whitespace()
+            && !is_closing_punctuation(next)
+            && (ch.is_whitespace() || is_cjk(ch) || is_cjk(next));
+
+        if can_break {
+            segments.push(&paragraph[start..end]);
+            start = end;
+        }
+    }
+
+    segments.push(&paragraph[start..]);
+    segments
+}
+
+// 한 단어가 max_width 보다 길면 글자 단위로 자름 (결합 문자는 앞 글자와 떼지 않음)
+fn split_long_segment(shaper: &Shaper, segment: &str, scale: Scale, max_width: f32) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut piece = String::new();
+
+    for ch in segment.chars() {
+        let mut candidate = piece.clone();
+        candidate.push(ch);
+        if !piece.is_empty() && !is_combining_mark(ch) && line_width(shaper, candidate.trim_end(), scale) > max_width {
+            pieces.push(std::mem::take(&mut piece));
+            piece.push(ch);
+        } else {
+            piece = candidate;
+        }
+    }
+
+    if !piece.is_empty() {
+        pieces.push(piece);
+    }
+    pieces
+}
+
+// 문단 하나를 max_width 안에 들어오도록 그리디 줄바꿈
+fn wrap_paragraph(shaper: &Shaper, paragraph: &str, scale: Scale, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for segment in break_segments(paragraph) {
+        let candidate = format!("{}{}", current, segment);
+        if line_width(shaper, candidate.trim_end(), scale) <= max_width {
+            current = candidate;
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(current.trim_end().to_string());
+        }
+
+        current = segment.trim_start().to_string();
+        if line_width(shaper, current.trim_end(), scale) > max_width {
+            let mut pieces = split_long_segment(shaper, &current, scale, max_width);
+            current = pieces.pop().unwrap_or_default();
+            lines.extend(pieces);
+        }
+    }
+
+    lines.push(current.trim_end().to_string());
+    lines
+}
+
+// \n 과 최대 너비 기준으로 줄 나누기
+fn break_lines(shaper: &Shaper, text: &str, font_size: f32, max_width: Option<f32>) -> Vec<String> {
+    let scale = Scale::uniform(font_size);
+    let normalized = text.replace("\r\n", "\n").replace('\r', "\n");
+
+    normalized
+        .split('\n')
+        .flat_map(|paragraph| match max_width {
+            Some(max_width) if !shaper.fonts.is_empty() => wrap_paragraph(shaper, paragraph, scale, max_width),
+            _ => vec![paragraph.to_string()],
+        })
+        .collect()
+}
+
+// 여러 줄 텍스트 배치 (줄바꿈, 정렬, 줄 간격 적용)
+// 첫 줄의 기준선은 주 폰트의 ascent 이므로 한 줄일 때는 draw_text_mut 와 같은 위치입니다.
+pub fn layout_text(fonts: &FontChain, text: &str, font_size: f32, options: &LayoutOptions) -> TextLayout {
+    let v_metrics = match fonts.primary() {
+        Some(primary) => primary.v_metrics(Scale::uniform(font_size)),
+        None => {
+            return TextLayout {
+                glyphs: Vec::new(),
+                metrics: TextMetrics::from_size(1, font_size as u32),
+            }
+        }
+    };
+    let scale = Scale::uniform(font_size);
+    let line_advance = (v_metrics.ascent - v_metrics.descent + v_metrics.line_gap) * options.line_height;
+
+    let shaper = Shaper::new(fonts);
+    let lines = break_lines(&shaper, text, font_size, options.max_width);
+    let widths: Vec<f32> = lines.iter().map(|line| line_width(&shaper, line, scale)).collect();
+    let block_width = widths.iter().cloned().fold(0.0, f32::max);
+
+    let mut glyphs = Vec::new();
+    for (i, (line, width)) in lines.iter().zip(&widths).enumerate() {
+        let offset_x = match options.align {
+            TextAlign::Left => 0.0,
+            TextAlign::Center => ((block_width - width) / 2.0).round(),
+            TextAlign::Right => (block_width - width).round(),
+        };
+        let baseline = v_metrics.ascent + line_advance * i as f32;
+        layout_line(&shaper, line, scale, offset_x, baseline, &mut glyphs);
+    }
+
+    let mut ink: Option<TextBounds> = None;
+
+    for glyph in &glyphs {
+        if let Some(bb) = glyph.pixel_bounding_box() {
+            let glyph_bounds = TextBounds::from_edges(bb.min.x, bb.min.y, bb.max.x, bb.max.y);
+            ink = Some(match ink {
+                Some(current) => current.union(&glyph_bounds),
+                None => glyph_bounds,
+            });
+        }
+    }
+
+    // 마지막 줄은 줄 간격 대신 descent 까지만 포함
+    let block_height = line_advance * (lines.len() - 1) as f32 + v_metrics.ascent - v_metrics.descent;
+    let advance_box = TextBounds::from_edges(0, 0, block_width.ceil() as i32, block_height.ceil() as i32);
+
+    let bounds = match ink {
+        Some(ink_bounds) => advance_box.union(&ink_bounds),
+        None => advance_box,
+    };
+
+    TextLayout {
+        glyphs,
+        metrics: TextMetrics {
+            advance_width: block_width,
+            ascent: v_metrics.ascent,
+            descent: v_metrics.descent,
+            ink_bounds: ink,
+            bounds,
+            rotated_bounds: None,
+            line_count: lines.len(),
+        },
+    }
+}
+
+// 텍스트 영역(여백 포함)이 이미지 안에 들어오도록 그리기 원점 조정
+// 텍스트가 이미지보다 크면 기존 동작과 같이 오른쪽/아래쪽 경계를 우선합니다.
+pub fn clamp_origin(origin: i32, offset: i32, extent: u32, limit: u32, margin: i32) -> i32 {
+    let min = margin - offset;
+    let max = limit as i32 - margin - offset - extent as i32;
+    origin.max(min).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fonts::FontRegistry;
+
+    // 시스템에 설치된 폰트로 배치 (폰트가 하나도 없는 환경에서는 None → 테스트 건너뜀)
+    fn system_fonts() -> Option<FontChain> {
+        FontRegistry::new().font_chain(None).ok().filter(|fonts| !fonts.is_empty())
+    }
+
+    const SCALE: Scale = Scale { x: 32.0, y: 32.0 };
+
+    #[test]
+    fn breaks_after_spaces_and_between_cjk_characters() {
+        assert_eq!(break_segments("hello big world"), vec!["hello ", "big ", "world"]);
+        assert_eq!(break_segments("안녕하세요"), vec!["안", "녕", "하", "세", "요"]);
+        assert_eq!(break_segments("ab한글cd"), vec!["ab", "한", "글", "cd"]);
+        // 닫는 문장부호와 공백은 앞 글자에 붙음
+        assert_eq!(break_segments("가, 나."), vec!["가, ", "나."]);
+        assert_eq!(break_segments(""), vec![""]);
+    }
+
+    #[test]
+    fn long_word_is_split_by_characters() {
+        let Some(fonts) = system_fonts() else { return };
+        let shaper = Shaper::new(&fonts);
+        let word = "abcdefghijklmnopqrstuvwxyz";
+        let max_width = line_width(&shaper, "abcde", SCALE) + 0.5;
+
+        let lines = wrap_paragraph(&shaper, &format!("xy {}", word), SCALE, max_width);
+        assert_eq!(lines[0], "xy");
+        assert_eq!(lines[1..].concat(), word);
+        assert!(lines.len() > 2);
+        for line in &lines {
+            assert!(line_width(&shaper, line, SCALE) <= max_width, "{:?}", lines);
+        }
+
+        // 한 글자도 들어가지 않으면 글자마다 한 줄
+        assert_eq!(wrap_paragraph(&shaper, "abc", SCALE, 1.0), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn cjk_text_wraps_between_characters() {
+        let Some(fonts) = system_fonts() else { return };
+        let shaper = Shaper::new(&fonts);
+        let max_width = line_width(&shaper, "가나", SCALE) + 0.5;
+
+        assert_eq!(wrap_paragraph(&shaper, "가나다라마", SCALE, max_width), vec!["가나", "다라", "마"]);
+        // 문장부호는 줄 앞으로 넘기지 않음
+        let lines = wrap_paragraph(&shaper, "가나다.", SCALE, max_width);
+        assert_eq!(lines.last().unwrap(), "다.");
+    }
+
+    #[test]
+    fn wrap_keeps_words_that_fit() {
+        let Some(fonts) = system_fonts() else { return };
+        let shaper = Shaper::new(&fonts);
+        let max_width = line_width(&shaper, "one two", SCALE) + 0.5;
+
+        assert_eq!(wrap_paragraph(&shaper, "one two three", SCALE, max_width), vec!["one two", "three"]);
+        assert_eq!(break_lines(&shaper, "one two\r\nthree", 32.0, None), vec!["one two", "three"]);
+    }
+
+    // ch 를 그린 글리프의 x 위치 (ch 를 그릴 폰트가 없으면 None)
+    fn glyph_x(fonts: &FontChain, glyphs: &[PositionedGlyph], ch: char) -> Option<f32> {
+        let font = fonts.get(fonts.font_index_for(ch));
+        let id = font.glyph(ch).id();
+        if id.0 == 0 {
+            return None;
+        }
+        glyphs.iter().find(|glyph| glyph.id() == id).map(|glyph| glyph.position().x)
+    }
+
+    #[test]
+    fn mixed_direction_runs_are_placed_in_visual_order() {
+        let Some(fonts) = system_fonts() else { return };
+        let shaper = Shaper::new(&fonts);
+
+        // 히브리어로 시작하는 문단: 오른쪽부터 "אבג", 그 왼쪽에 "xyz"
+        let mut glyphs = Vec::new();
+        layout_line(&shaper, "אבג xyz", SCALE, 0.0, 0.0, &mut glyphs);
+        let position = |ch| glyph_x(&fonts, &glyphs, ch);
+        let (Some(alef), Some(gimel), Some(x), Some(z)) = (position('א'), position('ג'), position('x'), position('z')) else {
+            return;
+        };
+        assert!(x < z && z < gimel && gimel < alef, "x {} z {} gimel {} alef {}", x, z, gimel, alef);
+
+        // 라틴 문자로 시작하면 왼쪽부터 "xyz", 히브리어 구간만 뒤집힘
+        let mut glyphs = Vec::new();
+        layout_line(&shaper, "xyz אבג", SCALE, 0.0, 0.0, &mut glyphs);
+        let position = |ch| glyph_x(&fonts, &glyphs, ch).unwrap();
+        assert!(position('x') < position('z') && position('z') < position('ג') && position('ג') < position('א'));
+    }
+
+    #[test]
+    fn clamp_origin_keeps_text_inside_image() {
+        // 텍스트 영역 (offset -4, 너비 50) 을 너비 200 이미지에 여백 10 으로
+        assert_eq!(clamp_origin(60, -4, 50, 200, 10), 60);
+        assert_eq!(clamp_origin(0, -4, 50, 200, 10), 14);
+        assert_eq!(clamp_origin(190, -4, 50, 200, 10), 144);
+    }
+
+    #[test]
+    fn clamp_origin_prefers_far_edge_when_text_is_larger() {
+        // 이미지보다 크면 오른쪽/아래쪽 경계에 맞춤 (왼쪽/위쪽이 잘림)
+        assert_eq!(clamp_origin(0, 0, 300, 200, 10), -110);
+        assert_eq!(clamp_origin(500, 0, 300, 200, 10), -110);
+        assert_eq!(clamp_origin(0, 0, 200, 200, 0), 0);
+    }
+}
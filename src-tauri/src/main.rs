@@ -3,23 +3,80 @@
     windows_subsystem = "windows"
 )]
 
-use image::{DynamicImage, GenericImageView, ImageFormat, Rgba, RgbImage, Rgb};
-use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut};
+use image::{DynamicImage, GenericImageView, ImageFormat, Rgba, RgbImage, RgbaImage, Rgb};
 use imageproc::rect::Rect;
-use rusttype::{Font, Scale};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fs;
 use std::io::Cursor;
 use std::path::Path;
-use tauri::command;
+use tauri::{command, AppHandle, Manager, State};
 use base64::{Engine as _, engine::general_purpose};
 
+mod batch;
+mod detect;
+mod effects;
+mod encode;
+mod filename;
+mod fonts;
+mod layout;
+mod metadata;
+mod orientation;
+mod output;
+mod placement;
+mod render;
+mod rotation;
+mod scan;
+mod sizing;
+mod style;
+mod tile;
+mod unicode;
+mod watermark;
+
+use batch::{BatchControl, BatchJob, BatchOptions, BatchSummary};
+use encode::EncodeOptions;
+use filename::{FileNameContext, FileNameTemplate};
+use fonts::{FontChain, FontInfo, FontRegistry};
+use layout::{LayoutOptions, TextBounds, TextLayout, TextMetrics};
+use metadata::{ImageMetadata, MetadataPolicy};
+use output::{ClaimedTargets, OutputOptions, OverwritePolicy, SaveOutcome};
+use placement::{PlacementOptions, TextPosition};
+use rotation::Rotation;
+use scan::{ScanOptions, ScannedFile};
+use sizing::FontSizing;
+use style::{TextStyle, TextStyleOptions};
+use tile::{Tile, TileOptions};
+use watermark::{LogoCache, Watermark, WatermarkOptions};
+
 #[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct ProcessResult {
     success: bool,
     error: Option<String>,
+    // 실제로 텍스트를 그리는 데 사용한 폰트
+    font_used: Option<String>,
+    // 덮어쓰기 정책에 따라 저장하지 않았으면 true (이유는 error)
+    #[serde(default)]
+    skipped: bool,
+    // 실제로 저장한 파일 경로 (auto-rename 이면 번호가 붙은 이름)
+    #[serde(default)]
+    output_file: Option<String>,
 }
 
+impl ProcessResult {
+    fn failed(error: String) -> Self {
+        ProcessResult {
+            success: false,
+            error: Some(error),
+            font_used: None,
+            skipped: false,
+            output_file: None,
+        }
+    }
+}
+
+const NO_FONT_ERROR: &str = "사용 가능한 폰트가 없습니다. 폰트를 설치하거나 내장 폰트(bundled-font) 포함 빌드를 사용하세요.";
+
 #[derive(Serialize, Deserialize)]
 struct ImageDimensions {
     width: u32,
@@ -27,27 +84,92 @@ struct ImageDimensions {
 }
 
 #[command]
+#[allow(clippy::too_many_arguments)]
 fn process_image(
+    fonts: State<'_, FontRegistry>,
+    logos: State<'_, LogoCache>,
     image_path: String,
     output_path: String,
     text: String,
     font_size: f32,
     position_x: f32,
     position_y: f32,
+    font_id: Option<String>,
+    style: Option<TextStyleOptions>,
+    placement: Option<PlacementOptions>,
+    font_sizing: Option<FontSizing>,
+    overwrite: Option<OverwritePolicy>,
+    file_name_template: Option<FileNameTemplate>,
+    encoding: Option<EncodeOptions>,
+    metadata: Option<MetadataPolicy>,
+    watermark: Option<WatermarkOptions>,
+    tile: Option<TileOptions>,
 ) -> ProcessResult {
+    // 지정한 폰트를 찾을 수 없으면 다른 폰트로 대체하지 않고 실패 처리
+    let font_chain = match fonts.font_chain(font_id.as_deref()) {
+        Ok(font_chain) => font_chain,
+        Err(e) => return ProcessResult::failed(e),
+    };
+    // font_sizing 이 있으면 이미지를 읽은 뒤 해상도에 맞춰 폰트 크기 계산
+    let sizing = match FontSizing::from_args(font_size, font_sizing) {
+        Ok(sizing) => sizing,
+        Err(e) => return ProcessResult::failed(e),
+    };
+    // placement 가 있으면 측정된 텍스트 크기로 Rust 에서 위치 계산
+    let position = match TextPosition::from_args(position_x, position_y, placement) {
+        Ok(position) => position,
+        Err(e) => return ProcessResult::failed(e),
+    };
+    let output = OutputOptions {
+        output_path,
+        overwrite: overwrite.unwrap_or_default(),
+        file_name_template,
+        encoding: encoding.unwrap_or_default(),
+        metadata: metadata.unwrap_or_default(),
+    };
+    if let Err(e) = output.encoding.validate() {
+        return ProcessResult::failed(e);
+    }
+    let (watermark, tile) = match load_layers(&logos, watermark.as_ref(), tile.as_ref()) {
+        Ok(layers) => layers,
+        Err(e) => return ProcessResult::failed(e),
+    };
+    let style = style.unwrap_or_default();
+    let settings = ImageSettings {
+        fonts: &font_chain,
+        style: &style,
+        sizing,
+        position,
+        output: &output,
+        watermark: watermark.as_ref(),
+        tile: tile.as_ref(),
+        claimed_targets: None,
+    };
+    let font_names = font_chain.names_used(&text);
+    let font_used = if font_names.is_empty() { None } else { Some(font_names.join(", ")) };
+    
     // 먼저 이미지 파일이 손상되었는지 확인
-    match image::open(&image_path) {
+    match open_image(&image_path) {
         Ok(_) => {
             // 이미지가 정상이면 처리 진행
-            match process_image_internal(&image_path, &output_path, &text, font_size, position_x, position_y) {
-                Ok(_) => ProcessResult {
+            let job = BatchJob { image_path: image_path.clone(), text, overwrite: None, subfolder: None };
+            match process_image_internal(&settings, &job, 0, &|| false) {
+                Ok(SaveOutcome::Written(path)) => ProcessResult {
                     success: true,
                     error: None,
+                    font_used,
+                    skipped: false,
+                    output_file: Some(path.to_string_lossy().to_string()),
                 },
-                Err(e) => ProcessResult {
-                    success: false,
-                    error: Some(e),
+                Ok(SaveOutcome::Cancelled) => ProcessResult::failed("취소되었습니다.".to_string()),
+                Ok(SaveOutcome::Skipped(reason)) => ProcessResult {
+                    success: true,
+                    error: Some(reason),
+                    font_used: None,
+                    skipped: true,
+                    output_file: None,
                 },
+                Err(e) => ProcessResult::failed(e),
             }
         },
         Err(e) => {
@@ -55,44 +177,155 @@ fn process_image(
             if error_msg.contains("invalid JPEG") || error_msg.contains("SOI marker") {
                 // 손상된 JPEG 파일은 건너뛰기
                 eprintln!("주의: 손상된 JPEG 파일 건너뛰기: {}", image_path);
-                ProcessResult {
-                    success: false,
-                    error: Some(format!("손상된 JPEG 파일로 건너뛰었습니다: {}", image_path)),
-                }
+                ProcessResult::failed(format!("손상된 JPEG 파일로 건너뛰었습니다: {}", image_path))
             } else {
-                ProcessResult {
-                    success: false,
-                    error: Some(format!("이미지 로드 실패: {}", e)),
-                }
+                ProcessResult::failed(format!("이미지 로드 실패: {}", e))
             }
         }
     }
 }
 
+// 여러 이미지를 병렬로 처리 (메인 스레드를 막지 않도록 별도 스레드에서 실행)
+// 작업마다 batch-progress 이벤트를 보내고, cancel_batch 로 중간에 멈출 수 있음
+#[command]
+async fn process_batch(app: AppHandle, jobs: Vec<BatchJob>, options: BatchOptions) -> Result<BatchSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let fonts = app.state::<FontRegistry>();
+        let logos = app.state::<LogoCache>();
+        let control = app.state::<BatchControl>();
+        batch::run_batch(&fonts, &logos, &control, &jobs, &options, |progress| {
+            if let Err(e) = app.emit_all(batch::PROGRESS_EVENT, progress) {
+                eprintln!("진행 상황 전송 실패: {:?}", e);
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("일괄 처리 실행 실패: {}", e))?
+}
+
+// 진행 중인 일괄 처리 취소 (진행 중이던 작업이 없으면 false)
+#[command]
+fn cancel_batch(control: State<'_, BatchControl>) -> bool {
+    control.cancel()
+}
+
+// 이미지마다 같은 설정 (process_image 는 한 장, 일괄 처리는 모든 작업에 공통)
+struct ImageSettings<'a> {
+    fonts: &'a FontChain,
+    style: &'a TextStyleOptions,
+    sizing: FontSizing,
+    position: TextPosition,
+    output: &'a OutputOptions,
+    // 텍스트보다 먼저 합성할 로고
+    watermark: Option<&'a Watermark>,
+    // 가장 먼저 이미지 전체에 까는 반복 워터마크
+    tile: Option<&'a Tile>,
+    // 일괄 처리에서 다른 이미지가 이미 쓰기로 한 결과 경로 (한 장만 처리할 때는 None)
+    claimed_targets: Option<&'a ClaimedTargets>,
+}
+
+// 이미지 한 장 처리: 원본 보호/덮어쓰기 확인 → 디코딩 후 그리기 → 취소 확인 → 저장
+// index 는 작업 목록에서의 위치 (파일명 템플릿의 {index})
 fn process_image_internal(
+    settings: &ImageSettings,
+    job: &BatchJob,
+    index: usize,
+    cancelled: &dyn Fn() -> bool,
+) -> Result<SaveOutcome, String> {
+    let output = settings.output;
+    let overwrite = job.overwrite.unwrap_or(output.overwrite);
+    // 저장 형식 (original 이면 파일 내용으로 판별한 입력 형식)
+    // 확장자가 저장 형식과 다르면 (형식 변환, ".jpg" 로 저장된 PNG 등) 저장 형식의 확장자를 붙임
+    let input_path = Path::new(&job.image_path);
+    let format = output.encoding.output_format(detect::sniff_file(input_path)?);
+    let output_extension = (!detect::extension_matches(input_path, format)).then(|| detect::preferred_extension(format));
+    if let Some(subfolder) = &job.subfolder {
+        scan::validate_subfolder(subfolder)?;
+    }
+    let mut file_name = FileNameContext {
+        image_path: &job.image_path,
+        text: &job.text,
+        index,
+        dimensions: None,
+        output_extension,
+        subfolder: job.subfolder.as_deref(),
+    };
+
+    // 파일명에 이미지 크기가 없으면 디코딩 전에 확인 (원본과 같은 파일이거나 건너뛸 파일이면 디코딩하지 않음)
+    let early_target = if output.needs_dimensions() {
+        None
+    } else {
+        let target = output.target_for(&file_name)?;
+        if let Some(reason) = output::precheck(&job.image_path, &target, overwrite)? {
+            return Ok(SaveOutcome::Skipped(reason));
+        }
+        Some(target)
+    };
+
+    let (result, metadata) = render_image(
+        settings.fonts,
+        settings.style,
+        &job.image_path,
+        &job.text,
+        &settings.sizing,
+        &settings.position,
+        settings.watermark,
+        settings.tile,
+        output.metadata,
+    )?;
+    // 그리는 동안 취소되었으면 저장하지 않음 (저장은 임시 파일에 쓴 뒤 이름을 바꾸므로 중간에 끊기지 않음)
+    if cancelled() {
+        return Ok(SaveOutcome::Cancelled);
+    }
+
+    let target = match early_target {
+        Some(target) => target,
+        None => {
+            file_name.dimensions = Some(result.dimensions());
+            let target = output.target_for(&file_name)?;
+            if let Some(reason) = output::precheck(&job.image_path, &target, overwrite)? {
+                return Ok(SaveOutcome::Skipped(reason));
+            }
+            target
+        }
+    };
+    // 덮어쓸 때는 같은 일괄 처리의 다른 이미지와 결과 파일이 겹치지 않는지 확인
+    // (rename / skip 은 저장할 때 먼저 저장한 파일을 보고 번호를 붙이거나 건너뜀)
+    let claim = match (overwrite, settings.claimed_targets) {
+        (OverwritePolicy::Overwrite, Some(claimed)) => Some(claimed.claim(&target, &job.image_path)?),
+        _ => None,
+    };
+    let outcome = save_image(&result, format, &output.encoding, &metadata, &job.image_path, &target, overwrite);
+    // 저장하지 못했으면 같은 경로의 다른 이미지가 저장할 수 있도록 놓아 줌
+    if let (Ok(SaveOutcome::Written(_)), Some(claim)) = (&outcome, claim) {
+        claim.keep();
+    }
+    outcome
+}
+
+// 이미지를 읽어 반복 워터마크, 로고, 텍스트 순서로 그림 (원본 메타데이터는 정책에 따라 같은 파일 내용에서 읽어 둠)
+#[allow(clippy::too_many_arguments)]
+fn render_image(
+    fonts: &FontChain,
+    style: &TextStyleOptions,
     image_path: &str,
-    output_path: &str,
     text: &str,
-    font_size: f32,
-    position_x: f32,
-    position_y: f32,
-) -> Result<(), String> {
+    sizing: &FontSizing,
+    position: &TextPosition,
+    watermark: Option<&Watermark>,
+    tile: Option<&Tile>,
+    metadata_policy: MetadataPolicy,
+) -> Result<(DynamicImage, ImageMetadata), String> {
     // 입력 값 검증
     if text.is_empty() {
         return Err("텍스트가 비어있습니다.".to_string());
     }
-    
-    if font_size <= 0.0 || font_size > 200.0 {
-        return Err("폰트 크기가 유효하지 않습니다.".to_string());
-    }
-    
-    if position_x < 0.0 || position_y < 0.0 {
-        return Err("위치 좌표가 유효하지 않습니다.".to_string());
-    }
+    let source = fs::read(image_path).map_err(|e| format!("이미지 로드 실패: {}", e))?;
+    let metadata = ImageMetadata::read(&source, metadata_policy);
 
-    // 이미지 로드 (에러 처리 개선)
-    let img = match image::open(image_path) {
-        Ok(img) => img,
+    // 이미지 로드 (에러 처리 개선), EXIF 방향대로 돌린 뒤 텍스트를 넣음
+    let mut img = match decode_image(&source) {
+        Ok(img) => metadata.orientation.apply(img),
         Err(e) => {
             let error_msg = format!("{}", e);
             if error_msg.contains("invalid JPEG") || error_msg.contains("SOI marker") {
@@ -102,70 +335,73 @@ fn process_image_internal(
             }
         }
     };
-    
-    let format = get_image_format(image_path)?;
+    apply_layers(&mut img, fonts, text, tile, watermark, 1.0);
     
     // 이미지에 텍스트 추가 (경량화된 버전 사용)
-    let result = add_text_to_image_simple(img, text, font_size, position_x, position_y)?;
-    
-    // 출력 파일 경로 생성
-    let input_filename = Path::new(image_path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| "파일명을 가져올 수 없습니다.".to_string())?;
-    
-    let output_file_path = Path::new(output_path).join(input_filename);
-    
-    // 출력 디렉토리가 존재하는지 확인
-    if let Some(parent) = output_file_path.parent() {
-        if !parent.exists() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("출력 디렉토리 생성 실패: {}", e))?;
-        }
+    let result = add_text_to_image_simple(img, fonts, style, text, sizing, position)?;
+    Ok((result, metadata))
+}
+
+// 텍스트 아래에 그릴 레이어 읽기 (로고 파일은 캐시에서)
+fn load_layers(
+    logos: &LogoCache,
+    watermark: Option<&WatermarkOptions>,
+    tile: Option<&TileOptions>,
+) -> Result<(Option<Watermark>, Option<Tile>), String> {
+    let watermark = watermark.map(|options| Watermark::load(options, logos)).transpose()?;
+    let tile = tile.map(|options| Tile::load(options, logos)).transpose()?;
+    Ok((watermark, tile))
+}
+
+// 반복 워터마크 → 로고 순서로 합성 (텍스트는 그 위에 그림)
+// margin_factor: 미리보기로 줄인 이미지에 그릴 때 로고의 px 여백에 곱할 비율
+fn apply_layers(
+    img: &mut DynamicImage,
+    fonts: &FontChain,
+    text: &str,
+    tile: Option<&Tile>,
+    watermark: Option<&Watermark>,
+    margin_factor: f32,
+) {
+    if let Some(tile) = tile {
+        tile.apply(img, fonts, text);
     }
-    
-    // 결과 저장 (품질 설정 개선)
-    match format {
-        ImageFormat::Jpeg => {
-            let mut output = std::fs::File::create(&output_file_path)
-                .map_err(|e| format!("파일 생성 실패: {}", e))?;
-            
-            let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut output, 90); // 95% -> 90%로 경량화
-            encoder.encode_image(&result)
-                .map_err(|e| format!("JPEG 인코딩 실패: {}", e))?;
-        },
-        _ => {
-            result
-                .save_with_format(&output_file_path, format)
-                .map_err(|e| format!("이미지 저장 실패: {}", e))?;
-        }
+    if let Some(watermark) = watermark {
+        watermark.apply(img, margin_factor);
     }
-    
-    Ok(())
 }
 
+// 이미지를 열고 EXIF 방향대로 돌림 (미리보기, 썸네일, 크기 확인용)
+fn open_image(image_path: &str) -> image::ImageResult<DynamicImage> {
+    let source = fs::read(image_path)?;
+    let img = decode_image(&source)?;
+    Ok(ImageMetadata::read(&source, MetadataPolicy::Strip).orientation.apply(img))
+}
+
+// 파일 내용으로 형식을 판별해 디코딩 (확장자가 틀리거나 없어도 읽음)
+fn decode_image(source: &[u8]) -> image::ImageResult<DynamicImage> {
+    image::io::Reader::new(Cursor::new(source)).with_guessed_format()?.decode()
+}
+
+// 임시 파일에 인코딩한 뒤 덮어쓰기 정책에 따라 target (또는 번호를 붙인 이름) 으로 저장
+fn save_image(
+    result: &DynamicImage,
+    format: ImageFormat,
+    encoding: &EncodeOptions,
+    metadata: &ImageMetadata,
+    image_path: &str,
+    target: &Path,
+    overwrite: OverwritePolicy,
+) -> Result<SaveOutcome, String> {
+    output::save_atomic(image_path, target, overwrite, |writer| {
+        encode::encode_image(result, format, encoding, metadata, writer)
+    })
+}
+
+// 입력 폴더의 이미지 목록 (options 가 없으면 기존처럼 바로 아래 파일만)
 #[command]
-fn get_image_files(folder_path: String) -> Result<Vec<String>, String> {
-    let paths = fs::read_dir(&folder_path).map_err(|e| format!("폴더 읽기 실패: {}", e))?;
-    
-    let mut image_paths = Vec::new();
-    
-    for path in paths {
-        let path = path.map_err(|e| format!("경로 읽기 실패: {}", e))?.path();
-        
-        // 파일인지 확인
-        if !path.is_file() {
-            continue;
-        }
-        
-        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        
-        if matches!(extension.to_lowercase().as_str(), "jpg" | "jpeg" | "png" | "bmp" | "gif" | "webp") {
-            image_paths.push(path.to_string_lossy().to_string());
-        }
-    }
-    
-    Ok(image_paths)
+fn get_image_files(folder_path: String, options: Option<ScanOptions>) -> Result<Vec<ScannedFile>, String> {
+    scan::scan_images(Path::new(&folder_path), &options.unwrap_or_default())
 }
 
 #[command]
@@ -176,7 +412,7 @@ fn get_image_dimensions(image_path: String) -> Result<ImageDimensions, String> {
     }
     
     // 여러 번 시도해보기
-    match image::open(&image_path) {
+    match open_image(&image_path) {
         Ok(img) => {
             let (width, height) = img.dimensions();
             
@@ -204,15 +440,30 @@ fn get_image_dimensions(image_path: String) -> Result<ImageDimensions, String> {
 }
 
 #[command]
+#[allow(clippy::too_many_arguments)]
 fn get_image_preview(
+    fonts: State<'_, FontRegistry>,
+    logos: State<'_, LogoCache>,
     image_path: String,
     text: String,
     font_size: f32,
     position_x: f32,
     position_y: f32,
+    font_id: Option<String>,
+    style: Option<TextStyleOptions>,
+    placement: Option<PlacementOptions>,
+    font_sizing: Option<FontSizing>,
+    watermark: Option<WatermarkOptions>,
+    tile: Option<TileOptions>,
 ) -> Result<String, String> {
+    let font_chain = fonts.font_chain(font_id.as_deref())?;
+    let style = style.unwrap_or_default();
+    let position = TextPosition::from_args(position_x, position_y, placement)?;
+    let sizing = FontSizing::from_args(font_size, font_sizing)?;
+    let (watermark, tile) = load_layers(&logos, watermark.as_ref(), tile.as_ref())?;
+    
     // 이미지 로드 (에러 처리 개선)
-    let img = match image::open(&image_path) {
+    let img = match open_image(&image_path) {
         Ok(img) => img,
         Err(e) => {
             let error_msg = format!("{}", e);
@@ -233,16 +484,23 @@ fn get_image_preview(
         let new_width = (original_width as f32 * scale_factor) as u32;
         let new_height = (original_height as f32 * scale_factor) as u32;
         
-        // 위치와 폰트 크기도 비례하여 조정
-        let scaled_x = position_x * scale_factor;
-        let scaled_y = position_y * scale_factor;
-        let scaled_font_size = font_size * scale_factor;
+        // 위치와 폰트 크기도 비례하여 조정 (auto 크기는 원본 해상도 기준으로 먼저 고정)
+        let scaled_position = position.scaled(scale_factor);
+        let scaled_sizing = sizing.pinned(original_width, original_height).scaled(scale_factor);
+        // 줄바꿈 위치가 원본과 같도록 줄바꿈 너비도 같은 비율로 조정
+        let scaled_style = TextStyleOptions {
+            max_width: style.max_width.map(|max_width| max_width * scale_factor),
+            ..style
+        };
         
-        let resized = img.resize_exact(new_width, new_height, image::imageops::FilterType::Lanczos3);
-        add_text_to_image(resized, &text, scaled_font_size, scaled_x, scaled_y)?
+        let mut resized = img.resize_exact(new_width, new_height, image::imageops::FilterType::Lanczos3);
+        apply_layers(&mut resized, &font_chain, &text, tile.as_ref(), watermark.as_ref(), scale_factor);
+        add_text_to_image(resized, &font_chain, &scaled_style, &text, &scaled_sizing, &scaled_position)?
     } else {
         // 원본 크기가 충분히 작으면 그대로 사용
-        add_text_to_image(img, &text, font_size, position_x, position_y)?
+        let mut img = img;
+        apply_layers(&mut img, &font_chain, &text, tile.as_ref(), watermark.as_ref(), 1.0);
+        add_text_to_image(img, &font_chain, &style, &text, &sizing, &position)?
     };
     
     // 이미지를 base64로 인코딩 (PNG 사용으로 품질 유지)
@@ -257,15 +515,30 @@ fn get_image_preview(
 
 // 경량화된 미리보기 함수 (저장과 동일한 로직)
 #[command]
+#[allow(clippy::too_many_arguments)]
 fn get_image_preview_lightweight(
+    fonts: State<'_, FontRegistry>,
+    logos: State<'_, LogoCache>,
     image_path: String,
     text: String,
     font_size: f32,
     position_x: f32,
     position_y: f32,
+    font_id: Option<String>,
+    style: Option<TextStyleOptions>,
+    placement: Option<PlacementOptions>,
+    font_sizing: Option<FontSizing>,
+    watermark: Option<WatermarkOptions>,
+    tile: Option<TileOptions>,
 ) -> Result<String, String> {
+    let font_chain = fonts.font_chain(font_id.as_deref())?;
+    let style = style.unwrap_or_default();
+    let position = TextPosition::from_args(position_x, position_y, placement)?;
+    let sizing = FontSizing::from_args(font_size, font_sizing)?;
+    let (watermark, tile) = load_layers(&logos, watermark.as_ref(), tile.as_ref())?;
+    
     // 이미지 로드 (에러 처리 개선)
-    let img = match image::open(&image_path) {
+    let mut img = match open_image(&image_path) {
         Ok(img) => img,
         Err(e) => {
             let error_msg = format!("{}", e);
@@ -277,8 +550,9 @@ fn get_image_preview_lightweight(
         }
     };
     
-    // 저장과 동일한 로직으로 텍스트 추가
-    let result = add_text_to_image_simple(img, &text, font_size, position_x, position_y)?;
+    // 저장과 동일한 로직으로 반복 워터마크, 로고, 텍스트 추가
+    apply_layers(&mut img, &font_chain, &text, tile.as_ref(), watermark.as_ref(), 1.0);
+    let result = add_text_to_image_simple(img, &font_chain, &style, &text, &sizing, &position)?;
     
     // 미리보기용으로만 크기 조정 (저장 로직과 분리)
     let (original_width, original_height) = result.dimensions();
@@ -310,7 +584,7 @@ fn get_image_thumbnail(image_path: String) -> Result<String, String> {
         return Err("이미지 파일이 존재하지 않습니다.".to_string());
     }
     
-    match image::open(&image_path) {
+    match open_image(&image_path) {
         Ok(img) => {
             // 썸네일 크기로 리사이즈 (150x150) - 품질 개선
             let thumbnail = img.resize(150, 150, image::imageops::FilterType::Lanczos3);
@@ -369,33 +643,16 @@ fn create_placeholder_thumbnail() -> Result<String, String> {
     Ok(format!("data:image/png;base64,{}", base64_string))
 }
 
-fn get_image_format(path: &str) -> Result<ImageFormat, String> {
-    let extension = Path::new(path)
-        .extension()
-        .and_then(|e| e.to_str())
-        .ok_or_else(|| "파일 확장자를 찾을 수 없습니다.".to_string())?;
-    
-    match extension.to_lowercase().as_str() {
-        "jpg" | "jpeg" => Ok(ImageFormat::Jpeg),
-        "png" => Ok(ImageFormat::Png),
-        "bmp" => Ok(ImageFormat::Bmp),
-        "gif" => Ok(ImageFormat::Gif),
-        "webp" => Ok(ImageFormat::WebP),
-        _ => Err(format!("지원하지 않는 이미지 형식: {}", extension)),
-    }
-}
-
 // 기존 텍스트 추가 함수 (성능 우선)
 fn add_text_to_image(
     mut img: DynamicImage,
+    fonts: &FontChain,
+    style: &TextStyleOptions,
     text: &str,
-    font_size: f32,
-    position_x: f32,
-    position_y: f32,
+    sizing: &FontSizing,
+    position: &TextPosition,
 ) -> Result<DynamicImage, String> {
-    if text.is_empty() {
-        return Err("텍스트가 비어있습니다.".to_string());
-    }
+    let text = &*overlay_text(text)?;
     
     let (img_width, img_height) = img.dimensions();
     
@@ -403,52 +660,54 @@ fn add_text_to_image(
         return Err("이미지 크기가 유효하지 않습니다.".to_string());
     }
     
-    // 안전한 폰트 크기 설정 (10px ~ 200px)
-    let safe_font_size = font_size.max(10.0).min(200.0);
+    // 지정하지 않은 스타일은 기존 미리보기 기본값 사용
+    let style = style.resolve_for_image(TextStyle::LEGACY, img_width)?;
+    
+    // 이미지 크기 기준 폰트 크기 계산 (10px ~ 이미지 높이)
+    let safe_font_size = resolve_font_size(fonts, &style, text, sizing, img_width, img_height);
     
-    // 텍스트 크기 추정
-    let estimated_text_width = estimate_text_width(text, safe_font_size);
-    let estimated_text_height = safe_font_size as u32;
-    let padding = 16;
+    // 실제 글리프 메트릭으로 텍스트 배치 및 크기 측정 (여러 줄 포함)
+    let text_layout = layout_text_block(fonts, text, safe_font_size, &style.layout);
+    let metrics = text_layout.metrics;
+    // 배경 박스 패딩보다 경계 여백이 작아지지 않도록
+    let padding = 16.max(style.padding as i32 * 2).max(style.effect_extent());
     
     // 한글의 경우 더 큰 패딩 적용
     let safe_padding = if text.chars().any(|c| matches!(c, '가'..='힣')) {
-        20 // 한글이 포함된 경우 더 큰 패딩
+        padding.max(20) // 한글이 포함된 경우 더 큰 패딩
     } else {
         padding
     };
     
-    // 안전한 위치 계산 (한글 고려 경계 체크)
-    let safe_x = (position_x as i32)
-        .max(safe_padding)
-        .min((img_width as i32).saturating_sub((estimated_text_width + safe_padding as u32) as i32));
-    let safe_y = (position_y as i32)
-        .max(safe_padding)
-        .min((img_height as i32).saturating_sub((estimated_text_height + safe_padding as u32) as i32));
-    
-    // 실제 폰트 렌더링을 시도해보고, 실패하면 대체 방식 사용
-    match render_text_with_font(&mut img, text, safe_font_size, safe_x, safe_y) {
-        Ok(_) => Ok(img),
-        Err(_) => {
-            // 폰트 렌더링 실패 시 대체 방식 사용
-            eprintln!("경고: 폰트 렌더링 실패, 대체 방식 사용");
-            render_text_alternative(&mut img, text, safe_font_size, safe_x, safe_y)?;
-            Ok(img)
-        }
+    // 안전한 위치 계산 (기준점 배치는 측정된 텍스트 영역으로 계산 후 경계 체크, 회전했으면 돌린 영역 기준)
+    let (text_bounds, outer_bounds) = placement_bounds(&style, &metrics, safe_padding);
+    let (origin_x, origin_y) = position.origin(img_width, img_height, &text_bounds);
+    let safe_x = layout::clamp_origin(origin_x, outer_bounds.x, outer_bounds.width, img_width, 0);
+    let safe_y = layout::clamp_origin(origin_y, outer_bounds.y, outer_bounds.height, img_height, 0);
+    
+    // 실제 폰트 렌더링, 색상 블록 대체 방식은 디버그 모드에서만 사용
+    if !fonts.is_empty() {
+        render_with_font(&mut img, &style, &text_layout, safe_x, safe_y)?;
+    } else if block_text_enabled() {
+        eprintln!("디버그: 폰트 없이 색상 블록으로 텍스트 표시");
+        render_text_alternative(&mut img, &style, text, safe_font_size, safe_x, safe_y, &metrics)?;
+    } else {
+        return Err(NO_FONT_ERROR.to_string());
     }
+    
+    Ok(img)
 }
 
 // 경량화된 텍스트 추가 함수 (미리보기와 저장 모두 동일한 로직)
 fn add_text_to_image_simple(
     mut img: DynamicImage,
+    fonts: &FontChain,
+    style: &TextStyleOptions,
     text: &str,
-    font_size: f32,
-    position_x: f32,
-    position_y: f32,
+    sizing: &FontSizing,
+    position: &TextPosition,
 ) -> Result<DynamicImage, String> {
-    if text.is_empty() {
-        return Err("텍스트가 비어있습니다.".to_string());
-    }
+    let text = &*overlay_text(text)?;
     
     let (img_width, img_height) = img.dimensions();
     
@@ -456,155 +715,153 @@ fn add_text_to_image_simple(
         return Err("이미지 크기가 유효하지 않습니다.".to_string());
     }
     
-    // 안전한 폰트 크기 설정 (10px ~ 200px)
-    let safe_font_size = font_size.max(10.0).min(200.0);
-    
-    // 🔧 간소화된 텍스트 크기 추정
-    let estimated_text_width = estimate_text_width_precise(text, safe_font_size);
-    let estimated_text_height = (safe_font_size * 1.2) as u32;
-    let padding = 4; // 고정 패딩
-    
-    // 🔧 간단한 위치 사용: JavaScript에서 이미 계산된 절대 좌표 사용
-    let safe_x = (position_x as i32).max(padding).min((img_width as i32) - (estimated_text_width as i32) - padding);
-    let safe_y = (position_y as i32).max(padding).min((img_height as i32) - (estimated_text_height as i32) - padding);
+    // 지정하지 않은 스타일은 저장용 기본값 사용
+    let style = style.resolve_for_image(TextStyle::SIMPLE, img_width)?;
+    
+    // 이미지 크기 기준 폰트 크기 계산 (10px ~ 이미지 높이)
+    let safe_font_size = resolve_font_size(fonts, &style, text, sizing, img_width, img_height);
+    
+    // 🔧 실제 글리프 메트릭으로 텍스트 배치 및 영역 측정 (여러 줄 포함)
+    let text_layout = layout_text_block(fonts, text, safe_font_size, &style.layout);
+    let metrics = text_layout.metrics;
+    let padding = (style.padding as i32).max(style.effect_extent()) + 2; // 배경 박스 패딩 또는 효과 범위 + 여유 2px
+    
+    // 🔧 절대 좌표 또는 측정된 텍스트 영역 기준 기준점 배치 (회전했으면 돌린 영역이 이미지 안에 들어오도록)
+    let (text_bounds, outer_bounds) = placement_bounds(&style, &metrics, padding);
+    let (origin_x, origin_y) = position.origin(img_width, img_height, &text_bounds);
+    let safe_x = layout::clamp_origin(origin_x, outer_bounds.x, outer_bounds.width, img_width, 0);
+    let safe_y = layout::clamp_origin(origin_y, outer_bounds.y, outer_bounds.height, img_height, 0);
+    
+    // 깔끔한 폰트 렌더링, 색상 블록 대체 방식은 디버그 모드에서만 사용
+    if !fonts.is_empty() {
+        render_with_font_simple(&mut img, &style, &text_layout, safe_x, safe_y)?;
+    } else if block_text_enabled() {
+        eprintln!("디버그: 폰트 없이 색상 블록으로 텍스트 표시");
+        render_text_alternative_simple(&mut img, &style, text, safe_font_size, safe_x, safe_y, &metrics)?;
+    } else {
+        return Err(NO_FONT_ERROR.to_string());
+    }
     
-    // 깔끔한 폰트 렌더링 시도, 실패하면 깔끔한 대체 방식
-    match render_text_simple(&mut img, text, safe_font_size, safe_x, safe_y) {
-        Ok(_) => Ok(img),
-        Err(_) => {
-            eprintln!("폰트 렌더링 실패, 깔끔한 대체 방식 사용");
-            render_text_alternative_simple(&mut img, text, safe_font_size, safe_x, safe_y)?;
-            Ok(img)
-        }
+    Ok(img)
+}
+
+// 기준점 배치에 쓸 텍스트 영역과 경계 체크에 쓸 여백 포함 영역 (회전했으면 텍스트 가운데 기준으로 돌린 영역)
+fn placement_bounds(style: &TextStyle, metrics: &TextMetrics, padding: i32) -> (TextBounds, TextBounds) {
+    let pivot = metrics.bounds.center();
+    (
+        style.rotation.rotate_bounds(&metrics.bounds, pivot),
+        style.rotation.rotate_bounds(&metrics.bounds.expanded(padding), pivot),
+    )
+}
+
+// 그릴 텍스트 (비어 있으면 오류)
+// 파일명에서 온 NFD 한글이 자모로 따로 그려지지 않도록 측정/그리기 전에 NFC 로 합침
+fn overlay_text(text: &str) -> Result<Cow<'_, str>, String> {
+    if text.is_empty() {
+        return Err("텍스트가 비어있습니다.".to_string());
     }
+    Ok(unicode::to_nfc(text))
 }
 
-// 한글 지원 폰트를 사용한 텍스트 렌더링
-fn render_text_with_font(
-    img: &mut DynamicImage,
-    text: &str,
-    font_size: f32,
-    x: i32,
-    y: i32,
-) -> Result<(), String> {
-    // 개선된 한글 지원 폰트 경로
-    let font_paths = vec![
-        // macOS 한글 폰트 (우선순위 높음)
-        "/System/Library/Fonts/Supplemental/AppleSDGothicNeo.ttc",
-        "/System/Library/Fonts/AppleSDGothicNeo.ttc",
-        "/Library/Fonts/AppleSDGothicNeo.ttc",
-        "/System/Library/Fonts/AppleGothic.ttf",
-        "/Library/Fonts/AppleGothic.ttf",
-        
-        // macOS 기본 한글 지원 폰트
-        "/System/Library/Fonts/Helvetica.ttc",
-        "/System/Library/Fonts/ArialUnicodeMS.ttf",
-        "/System/Library/Fonts/PingFang.ttc",
-        
-        // Windows 한글 폰트
-        "C:/Windows/Fonts/malgun.ttf",     // 맑은 고딕
-        "C:/Windows/Fonts/batang.ttc",     // 바탕
-        "C:/Windows/Fonts/gulim.ttc",      // 굴림
-        
-        // Linux 한글 폰트
-        "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
-        "/usr/share/fonts/truetype/nanum/NanumBarunGothic.ttf",
-        "/usr/share/fonts/truetype/noto/NotoSansCJK-Regular.ttc",
-        "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
-        
-        // 기본 폰트들
-        "/System/Library/Fonts/Arial.ttf",
-        "/Library/Fonts/Arial.ttf",
-        "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
-        "/usr/share/fonts/TTF/DejaVuSans.ttf",
-    ];
-    
-    for font_path in font_paths {
-        if let Ok(font_data) = std::fs::read(font_path) {
-            if let Some(font) = Font::try_from_vec(font_data) {
-                return render_with_font(img, &font, text, font_size, x, y);
-            }
+// 크기 방식에 따라 폰트 크기 계산 (fit-width 는 실제 배치한 텍스트 블록 너비 기준)
+fn resolve_font_size(fonts: &FontChain, style: &TextStyle, text: &str, sizing: &FontSizing, img_width: u32, img_height: u32) -> f32 {
+    // 자동 줄바꿈 너비는 px 고정이라 기준 크기(100px)에서 재면 줄이 다르게 나뉘므로 줄바꿈 없이 측정
+    let unwrapped = LayoutOptions { max_width: None, ..style.layout };
+    sizing.resolve(img_width, img_height, |font_size| {
+        layout_text_block(fonts, text, font_size, &unwrapped).metrics.bounds.width as f32
+    })
+}
+
+// 폰트가 없을 때 글자 대신 색상 블록을 그리는 것은 명시적인 디버그 빌드에서만 허용
+// (일반 빌드에서 조용히 블록이 찍힌 결과물이 나가지 않도록)
+fn block_text_enabled() -> bool {
+    cfg!(feature = "debug-text-blocks")
+}
+
+// 폰트가 있으면 대체 폰트까지 포함한 실제 글리프 배치, 없으면 대체 렌더링과 같은 추정치 사용
+fn layout_text_block(fonts: &FontChain, text: &str, font_size: f32, options: &LayoutOptions) -> TextLayout {
+    if fonts.is_empty() {
+        TextLayout {
+            glyphs: Vec::new(),
+            metrics: TextMetrics::from_size(estimate_text_width_precise(text, font_size), font_size as u32),
         }
+    } else {
+        layout::layout_text(fonts, text, font_size, options)
     }
-    
-    Err("한글 지원 폰트를 찾을 수 없습니다.".to_string())
 }
 
-// 깔끔한 한글 지원 폰트 렌더링 (효과 최소화)
-fn render_text_simple(
-    img: &mut DynamicImage,
-    text: &str,
+#[command]
+fn measure_text(
+    fonts: State<'_, FontRegistry>,
+    text: String,
     font_size: f32,
-    x: i32,
-    y: i32,
-) -> Result<(), String> {
-    // 개선된 한글 지원 폰트 경로 (대체 함수와 동일)
-    let font_paths = vec![
-        // macOS 한글 폰트 (우선순위 높음)
-        "/System/Library/Fonts/Supplemental/AppleSDGothicNeo.ttc",
-        "/System/Library/Fonts/AppleSDGothicNeo.ttc",
-        "/Library/Fonts/AppleSDGothicNeo.ttc",
-        "/System/Library/Fonts/AppleGothic.ttf",
-        "/Library/Fonts/AppleGothic.ttf",
-        
-        // macOS 기본 한글 지원 폰트
-        "/System/Library/Fonts/Helvetica.ttc",
-        "/System/Library/Fonts/ArialUnicodeMS.ttf",
-        "/System/Library/Fonts/PingFang.ttc",
-        
-        // Windows 한글 폰트
-        "C:/Windows/Fonts/malgun.ttf",     // 맑은 고딕
-        "C:/Windows/Fonts/batang.ttc",     // 바탕
-        "C:/Windows/Fonts/gulim.ttc",      // 굴림
-        
-        // Linux 한글 폰트
-        "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
-        "/usr/share/fonts/truetype/nanum/NanumBarunGothic.ttf",
-        "/usr/share/fonts/truetype/noto/NotoSansCJK-Regular.ttc",
-        "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
-        
-        // 기본 폰트들
-        "/System/Library/Fonts/Arial.ttf",
-        "/Library/Fonts/Arial.ttf",
-        "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
-        "/usr/share/fonts/TTF/DejaVuSans.ttf",
-    ];
-    
-    for font_path in font_paths {
-        if let Ok(font_data) = std::fs::read(font_path) {
-            if let Some(font) = Font::try_from_vec(font_data) {
-                return render_with_font_simple(img, &font, text, font_size, x, y);
-            }
-        }
+    font_id: Option<String>,
+    style: Option<TextStyleOptions>,
+) -> Result<TextMetrics, String> {
+    if text.is_empty() {
+        return Err("텍스트가 비어있습니다.".to_string());
+    }
+    
+    let font_chain = fonts.font_chain(font_id.as_deref())?;
+    if font_chain.is_empty() && !block_text_enabled() {
+        return Err(NO_FONT_ERROR.to_string());
+    }
+    
+    if !(font_size.is_finite() && font_size > 0.0) {
+        return Err("폰트 크기가 유효하지 않습니다.".to_string());
     }
     
-    Err("한글 지원 폰트를 찾을 수 없습니다.".to_string())
+    // 줄바꿈/정렬/줄 간격은 저장할 때와 같은 값으로 측정 (그릴 때와 같이 NFC 로 합친 텍스트)
+    let style = style.unwrap_or_default().resolve(TextStyle::SIMPLE)?;
+    let text = unicode::to_nfc(&text);
+    let mut metrics = layout_text_block(&font_chain, &text, font_size.max(sizing::MIN_FONT_SIZE), &style.layout).metrics;
+    if !style.rotation.is_none() {
+        metrics.rotated_bounds = Some(style.rotation.rotate_bounds(&metrics.bounds, metrics.bounds.center()));
+    }
+    Ok(metrics)
+}
+
+// 설치된 폰트 목록 (user_font_dir 를 넘기면 해당 폴더도 함께 스캔, 빈 문자열이면 해제)
+// 처음에는 폰트 폴더 전체를 스캔하므로 메인 스레드를 막지 않도록 별도 스레드에서 실행
+#[command]
+async fn list_fonts(app: AppHandle, user_font_dir: Option<String>) -> Result<Vec<FontInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let fonts = app.state::<FontRegistry>();
+        if let Some(dir) = user_font_dir {
+            fonts.set_user_font_dir(if dir.is_empty() { None } else { Some(dir.into()) });
+        }
+
+        fonts.list_fonts()
+    })
+    .await
+    .map_err(|e| format!("폰트 목록 읽기 실패: {}", e))
 }
 
 // 폰트를 사용한 실제 렌더링
 fn render_with_font(
     img: &mut DynamicImage,
-    font: &Font,
-    text: &str,
-    font_size: f32,
+    style: &TextStyle,
+    text_layout: &TextLayout,
     x: i32,
     y: i32,
 ) -> Result<(), String> {
-    let scale = Scale::uniform(font_size);
-    let color = Rgba([255u8, 0u8, 0u8, 255u8]); // 빨간색 텍스트
-    
-    // 배경 그리기
-    let text_width = estimate_text_width(text, font_size);
-    let text_height = font_size as u32;
-    let padding = 8;
-    
-    let bg_rect = Rect::at(x - padding, y - padding)
-        .of_size(text_width + (padding * 2) as u32, text_height + (padding * 2) as u32);
+    if !style.rotation.is_none() {
+        return render_rotated(img, style, text_layout, x, y, render_with_font);
+    }
+    let metrics = &text_layout.metrics;
     
-    draw_filled_rect_mut(img, bg_rect, Rgba([255u8, 255u8, 255u8, 220u8])); // 흰색 배경
+    // 배경 그리기 (측정된 텍스트 영역 + 패딩)
+    if let Some(background) = style.background {
+        let padding = style.padding as i32;
+        
+        let bg_rect = Rect::at(x + metrics.bounds.x - padding, y + metrics.bounds.y - padding)
+            .of_size(metrics.bounds.width + (padding * 2) as u32, metrics.bounds.height + (padding * 2) as u32);
+        
+        render::fill_rect(img, bg_rect, background);
+    }
     
-    // 텍스트 그리기
-    draw_text_mut(img, color, x, y, scale, font, text);
+    // 텍스트 그리기 (글자마다 글리프가 있는 폰트 사용)
+    render::draw_text(img, style, x, y, &text_layout.glyphs);
     
     Ok(())
 }
@@ -612,45 +869,62 @@ fn render_with_font(
 // 🔧 텍스트 박스 문제 해결: 텍스트에 정확히 맞는 배경 박스
 fn render_with_font_simple(
     img: &mut DynamicImage,
-    font: &Font,
-    text: &str,
-    font_size: f32,
+    style: &TextStyle,
+    text_layout: &TextLayout,
     x: i32,
     y: i32,
 ) -> Result<(), String> {
-    let scale = Scale::uniform(font_size);
-    let text_color = Rgba([255u8, 0u8, 0u8, 255u8]); // 빨간색 텍스트
-    
-    // 🔧 텍스트에 딱 맞는 크기 계산
-    let text_width = estimate_text_width_precise(text, font_size);
-    let text_height = font_size as u32; // 높이는 폰트 크기와 동일
-    let padding = 2; // 적절한 패딩
-    
-    // 🔧 배경 박스를 텍스트보다 약간 크게
-    let bg_width = text_width + (padding * 2);
-    let bg_height = text_height + padding;
-    
-    // 배경 박스 위치 (패딩 고려)
-    let bg_x = x - padding as i32;
-    let bg_y = y - (padding / 2) as i32;
-    
-    let bg_rect = Rect::at(bg_x, bg_y).of_size(bg_width, bg_height);
+    if !style.rotation.is_none() {
+        return render_rotated(img, style, text_layout, x, y, render_with_font_simple);
+    }
+    // 🔧 글리프 메트릭으로 측정한 실제 텍스트 영역 (여러 줄이면 블록 전체)
+    let text_bounds = text_layout.metrics.bounds;
     
-    // 깔끔한 흰색 배경
-    draw_filled_rect_mut(img, bg_rect, Rgba([255u8, 255u8, 255u8, 245u8])); // 약간 불투명
+    if let Some(background) = style.background {
+        let padding = style.padding;
+        
+        // 🔧 배경 박스를 텍스트보다 약간 크게 (세로 패딩은 가로의 절반)
+        let bg_width = text_bounds.width + (padding * 2);
+        let bg_height = text_bounds.height + padding;
+        
+        // 배경 박스 위치 (패딩 고려)
+        let bg_x = x + text_bounds.x - padding as i32;
+        let bg_y = y + text_bounds.y - (padding / 2) as i32;
+        
+        let bg_rect = Rect::at(bg_x, bg_y).of_size(bg_width, bg_height);
+        render::fill_rect(img, bg_rect, background);
+    }
     
-    // 텍스트 그리기
-    draw_text_mut(img, text_color, x, y, scale, font, text);
+    // 텍스트 그리기 (글자마다 글리프가 있는 폰트 사용)
+    render::draw_text(img, style, x, y, &text_layout.glyphs);
     
     Ok(())
 }
 
-// 텍스트 너비 추정
-fn estimate_text_width(text: &str, font_size: f32) -> u32 {
-    (text.chars().count() as f32 * font_size * 0.6) as u32
+// 회전한 텍스트: 배경 박스와 효과까지 투명한 라벨에 똑바로 그린 뒤 텍스트 영역 가운데를 중심으로 돌려 합성
+// (x, y) 는 돌리기 전 그리기 원점
+fn render_rotated(
+    img: &mut DynamicImage,
+    style: &TextStyle,
+    text_layout: &TextLayout,
+    x: i32,
+    y: i32,
+    render_upright: fn(&mut DynamicImage, &TextStyle, &TextLayout, i32, i32) -> Result<(), String>,
+) -> Result<(), String> {
+    let bounds = text_layout.metrics.bounds;
+    // 배경 박스 패딩과 효과가 잘리지 않을 만큼 넓힌 라벨 영역
+    let label_area = bounds.expanded((style.padding as i32 * 2).max(style.effect_extent()) + 2);
+    let mut label = DynamicImage::ImageRgba8(RgbaImage::new(label_area.width, label_area.height));
+    let upright = TextStyle { rotation: Rotation::NONE, ..*style };
+    render_upright(&mut label, &upright, text_layout, -label_area.x, -label_area.y)?;
+
+    let (center_x, center_y) = bounds.center();
+    let pivot = (center_x - label_area.x as f32, center_y - label_area.y as f32);
+    style.rotation.draw(img, &label.into_rgba8(), pivot, (x as f32 + center_x, y as f32 + center_y));
+    Ok(())
 }
 
-// 🔧 텍스트 박스 크기 문제 완전 해결: 실제 텍스트에 최적화된 너비 계산
+// 폰트가 없을 때만 사용하는 문자별 너비 추정 (대체 렌더링용)
 fn estimate_text_width_precise(text: &str, font_size: f32) -> u32 {
     if text.is_empty() {
         return 10; // 최소 너비
@@ -719,24 +993,28 @@ fn estimate_text_width_precise(text: &str, font_size: f32) -> u32 {
 // 대체 텍스트 렌더링 (폰트 실패 시)
 fn render_text_alternative(
     img: &mut DynamicImage,
+    style: &TextStyle,
     text: &str,
     font_size: f32,
     x: i32,
     y: i32,
+    metrics: &TextMetrics,
 ) -> Result<(), String> {
-    let text_width = estimate_text_width(text, font_size);
-    let text_height = font_size as u32;
-    let padding = 8;
-    
-    // 배경 박스 (흰색)
-    let bg_rect = Rect::at(x - padding, y - padding)
-        .of_size(text_width + (padding * 2) as u32, text_height + (padding * 2) as u32);
-    
-    draw_filled_rect_mut(img, bg_rect, Rgba([255u8, 255u8, 255u8, 220u8])); // 흰새 배경
+    let text_width = metrics.bounds.width;
+    let text_height = metrics.bounds.height;
+    
+    // 배경 박스
+    if let Some(background) = style.background {
+        let padding = style.padding as i32;
+        let bg_rect = Rect::at(x - padding, y - padding)
+            .of_size(text_width + (padding * 2) as u32, text_height + (padding * 2) as u32);
+        
+        render::fill_rect(img, bg_rect, background);
+    }
     
     // 텍스트 영역 기본 채우기 (빨간색)
     let text_rect = Rect::at(x, y).of_size(text_width, text_height);
-    draw_filled_rect_mut(img, text_rect, Rgba([255u8, 200u8, 200u8, 255u8])); // 연한 빨간색 기본 영역
+    render::fill_rect(img, text_rect, Rgba([255u8, 200u8, 200u8, 255u8])); // 연한 빨간색 기본 영역
     
     // 텍스트 내용을 나타내는 실제 문자들을 간단한 사각형으로 표현
     let char_size = (font_size * 0.7) as u32;
@@ -766,7 +1044,7 @@ fn render_text_alternative(
                 _ => Rgba([120u8, 0u8, 0u8, 255u8]), // 어두운 빨간색 - 기타
             };
             
-            draw_filled_rect_mut(img, char_rect, color);
+            render::fill_rect(img, char_rect, color);
         }
     }
     
@@ -776,20 +1054,24 @@ fn render_text_alternative(
 // 🔧 텍스트 박스 문제 해결: 대체 렌더링도 정확한 크기로
 fn render_text_alternative_simple(
     img: &mut DynamicImage,
+    style: &TextStyle,
     text: &str,
     font_size: f32,
     x: i32,
     y: i32,
+    metrics: &TextMetrics,
 ) -> Result<(), String> {
-    let text_width = estimate_text_width_precise(text, font_size);
-    let text_height = font_size as u32;
-    let padding = 2; // 적절한 패딩
+    let text_width = metrics.bounds.width;
+    let text_height = metrics.bounds.height;
     
     // 🔧 텍스트보다 약간 큰 배경 박스
-    let bg_rect = Rect::at(x - padding, y - (padding / 2))
-        .of_size(text_width + (padding * 2) as u32, text_height + padding as u32);
-    
-    draw_filled_rect_mut(img, bg_rect, Rgba([255u8, 255u8, 255u8, 240u8])); // 약간 투명한 배경
+    if let Some(background) = style.background {
+        let padding = style.padding as i32;
+        let bg_rect = Rect::at(x - padding, y - (padding / 2))
+            .of_size(text_width + (padding * 2) as u32, text_height + padding as u32);
+        
+        render::fill_rect(img, bg_rect, background);
+    }
     
     // 🔧 문자별 렌더링도 더 정확하게
     let chars: Vec<char> = text.chars().collect();
@@ -831,7 +1113,7 @@ fn render_text_alternative_simple(
                 _ => Rgba([160u8, 20u8, 20u8, 255u8]),
             };
             
-            draw_filled_rect_mut(img, char_rect, color);
+            render::fill_rect(img, char_rect, color);
         }
         
         current_x += char_width as i32; // 간격 최소화
@@ -842,13 +1124,20 @@ fn render_text_alternative_simple(
 
 fn main() {
     tauri::Builder::default()
+        .manage(FontRegistry::new())
+        .manage(LogoCache::new())
+        .manage(BatchControl::new())
         .invoke_handler(tauri::generate_handler![
             process_image,
+            process_batch,
+            cancel_batch,
             get_image_files,
             get_image_dimensions,
             get_image_preview,
             get_image_preview_lightweight,
-            get_image_thumbnail
+            get_image_thumbnail,
+            measure_text,
+            list_fonts
         ])
         .run(tauri::generate_context!())
         .expect("오류: Tauri 애플리케이션을 실행하는 중 오류가 발생했습니다.");
@@ -0,0 +1,341 @@
+// 원본 메타데이터 (EXIF, ICC 색상 프로필, XMP) 를 결과 파일로 옮김
+//
+// 픽셀을 디코딩/재인코딩하면 메타데이터가 모두 사라지므로 원본 파일에서 따로 읽어 둡니다.
+// JPEG 은 인코더에 세그먼트로 넘기고 (encode.rs), PNG / WebP 는 인코딩한 결과에 청크를 끼워 넣습니다.
+// TIFF 등 다른 형식으로 저장하면 메타데이터는 옮기지 않습니다.
+
+use image::ImageFormat;
+use img_parts::jpeg::{markers, Jpeg};
+use img_parts::png::{Png, PngChunk};
+use img_parts::riff::{RiffChunk, RiffContent};
+use img_parts::webp::{self, WebP};
+use img_parts::{Bytes, DynImage, ImageEXIF, ImageICC};
+use serde::Deserialize;
+use std::io::Write;
+
+use crate::orientation::{self, Orientation};
+
+// JPEG APP1 / WebP EXIF 청크 앞에 붙는 식별자
+pub const EXIF_PREFIX: &[u8] = b"Exif\0\0";
+// JPEG APP1 XMP 식별자
+pub const XMP_JPEG_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+// PNG iTXt XMP 키워드
+const XMP_PNG_KEYWORD: &[u8] = b"XML:com.adobe.xmp";
+// WebP VP8X 플래그
+const VP8X_ICC: u8 = 0b0010_0000;
+const VP8X_ALPHA: u8 = 0b0001_0000;
+const VP8X_EXIF: u8 = 0b0000_1000;
+const VP8X_XMP: u8 = 0b0000_0100;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetadataPolicy {
+    // EXIF (촬영 정보, 저작권), ICC, XMP 모두 유지
+    #[default]
+    KeepAll,
+    // 색상 프로필만 유지 (카메라 정보, GPS 등은 제거)
+    #[serde(alias = "icc-only")]
+    ColorProfile,
+    // 모두 제거
+    Strip,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImageMetadata {
+    pub icc: Option<Bytes>,
+    // TIFF 헤더부터 ("Exif\0\0" 제외)
+    pub exif: Option<Bytes>,
+    // XMP 패킷 (XML)
+    pub xmp: Option<Bytes>,
+    // 원본 EXIF 의 방향 (정책과 관계없이 읽음, 남기는 EXIF 의 태그는 정방향으로 바꿔 둠)
+    pub orientation: Orientation,
+}
+
+impl ImageMetadata {
+    // 원본 파일 내용에서 정책에 해당하는 메타데이터만 읽음 (JPEG / PNG / WebP 외에는 없음, TIFF 는 방향만)
+    // 메타데이터가 손상되어도 이미지 처리는 계속하도록 오류는 로그만 남김
+    pub fn read(source: &[u8], policy: MetadataPolicy) -> Self {
+        let mut metadata = match DynImage::from_bytes(Bytes::copy_from_slice(source)) {
+            Ok(Some(DynImage::Jpeg(jpeg))) => read_jpeg(&jpeg),
+            Ok(Some(DynImage::Png(png))) => read_png(&png),
+            Ok(Some(DynImage::WebP(webp))) => read_webp(&webp),
+            // TIFF 파일은 EXIF 와 같은 구조라 IFD0 의 방향 태그를 바로 읽음 (TIFF 가 아니면 Normal)
+            Ok(None) => ImageMetadata {
+                orientation: Orientation::from_exif(source),
+                ..ImageMetadata::default()
+            },
+            Err(e) => {
+                eprintln!("메타데이터 읽기 실패 (메타데이터 없이 저장): {}", e);
+                ImageMetadata::default()
+            }
+        };
+
+        if let Some(exif) = &metadata.exif {
+            metadata.orientation = Orientation::from_exif(exif);
+            metadata.exif = Some(Bytes::from(orientation::normalize_exif(exif, metadata.orientation)));
+        }
+
+        match policy {
+            MetadataPolicy::KeepAll => {}
+            MetadataPolicy::ColorProfile => {
+                metadata.exif = None;
+                metadata.xmp = None;
+            }
+            MetadataPolicy::Strip => {
+                metadata.icc = None;
+                metadata.exif = None;
+                metadata.xmp = None;
+            }
+        }
+        metadata
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.icc.is_none() && self.exif.is_none() && self.xmp.is_none()
+    }
+
+    // 인코딩된 PNG / WebP 에 메타데이터 청크를 넣어서 씀 (다른 형식은 그대로)
+    pub fn write_into<W: Write>(&self, encoded: Vec<u8>, format: ImageFormat, writer: &mut W) -> Result<(), String> {
+        let written = match format {
+            ImageFormat::Png => {
+                let png = Png::from_bytes(Bytes::from(encoded)).map_err(|e| format!("PNG 메타데이터 쓰기 실패: {}", e))?;
+                self.embed_png(png).encoder().write_to(writer)
+            }
+            ImageFormat::WebP => {
+                let webp = WebP::from_bytes(Bytes::from(encoded)).map_err(|e| format!("WebP 메타데이터 쓰기 실패: {}", e))?;
+                self.embed_webp(&webp)?.encoder().write_to(writer)
+            }
+            _ => writer.write_all(&encoded).map(|_| 0),
+        };
+        written
+            .map(|_| ())
+            .map_err(|e| format!("파일 쓰기 실패: {}", e))
+    }
+
+    // iCCP 는 IHDR 바로 뒤, eXIf / iTXt 는 IDAT 앞
+    fn embed_png(&self, mut png: Png) -> Png {
+        png.set_icc_profile(self.icc.clone());
+
+        let mut chunks = Vec::new();
+        if let Some(exif) = &self.exif {
+            chunks.push(PngChunk::new(*b"eXIf", exif.clone()));
+        }
+        if let Some(xmp) = &self.xmp {
+            // 키워드\0, 압축 안 함(0), 압축 방식(0), 언어\0, 번역된 키워드\0, 본문
+            let mut contents = Vec::with_capacity(XMP_PNG_KEYWORD.len() + 5 + xmp.len());
+            contents.extend_from_slice(XMP_PNG_KEYWORD);
+            contents.extend_from_slice(&[0, 0, 0, 0, 0]);
+            contents.extend_from_slice(xmp);
+            chunks.push(PngChunk::new(*b"iTXt", Bytes::from(contents)));
+        }
+
+        let idat = png.chunks().iter().position(|chunk| &chunk.kind() == b"IDAT").unwrap_or(1);
+        png.chunks_mut().splice(idat..idat, chunks);
+        png
+    }
+
+    // 메타데이터 청크는 확장 형식(VP8X)에서만 쓸 수 있음
+    // 순서: VP8X, ICCP, 이미지 데이터 (ALPH + VP8 또는 VP8L), EXIF, XMP
+    fn embed_webp(&self, webp: &WebP) -> Result<RiffChunk, String> {
+        let (width, height) = webp
+            .dimensions()
+            .ok_or_else(|| "WebP 이미지 크기를 읽을 수 없습니다.".to_string())?;
+
+        let mut flags = 0;
+        if has_webp_alpha(webp) {
+            flags |= VP8X_ALPHA;
+        }
+
+        let mut subchunks = Vec::new();
+        if let Some(icc) = &self.icc {
+            flags |= VP8X_ICC;
+            subchunks.push(RiffChunk::new(webp::CHUNK_ICCP, RiffContent::Data(icc.clone())));
+        }
+        subchunks.extend(
+            webp.chunks()
+                .iter()
+                .filter(|chunk| ![webp::CHUNK_VP8X, webp::CHUNK_ICCP, webp::CHUNK_EXIF, webp::CHUNK_XMP].contains(&chunk.id()))
+                .cloned(),
+        );
+        if let Some(exif) = &self.exif {
+            flags |= VP8X_EXIF;
+            subchunks.push(RiffChunk::new(webp::CHUNK_EXIF, RiffContent::Data(exif.clone())));
+        }
+        if let Some(xmp) = &self.xmp {
+            flags |= VP8X_XMP;
+            subchunks.push(RiffChunk::new(webp::CHUNK_XMP, RiffContent::Data(xmp.clone())));
+        }
+
+        // 플래그 1바이트 + 예약 3바이트 + (너비 - 1), (높이 - 1) 각 24비트
+        let mut vp8x = vec![flags, 0, 0, 0];
+        vp8x.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+        vp8x.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+        subchunks.insert(0, RiffChunk::new(webp::CHUNK_VP8X, RiffContent::Data(Bytes::from(vp8x))));
+
+        Ok(RiffChunk::new(*b"RIFF", RiffContent::List { kind: Some(*b"WEBP"), subchunks }))
+    }
+}
+
+fn read_jpeg(jpeg: &Jpeg) -> ImageMetadata {
+    let xmp = jpeg
+        .segments_by_marker(markers::APP1)
+        .find(|segment| segment.contents().starts_with(XMP_JPEG_HEADER))
+        .map(|segment| segment.contents().slice(XMP_JPEG_HEADER.len()..));
+
+    ImageMetadata {
+        icc: jpeg.icc_profile(),
+        exif: jpeg.exif(),
+        xmp,
+        ..ImageMetadata::default()
+    }
+}
+
+fn read_png(png: &Png) -> ImageMetadata {
+    let xmp = png
+        .chunks_by_type(*b"iTXt")
+        .find_map(|chunk| png_xmp_text(chunk.contents()));
+
+    ImageMetadata {
+        icc: png.icc_profile(),
+        exif: png.exif(),
+        xmp,
+        ..ImageMetadata::default()
+    }
+}
+
+// 압축하지 않은 XMP iTXt 청크의 본문
+fn png_xmp_text(contents: &Bytes) -> Option<Bytes> {
+    // 키워드\0 + 압축 안 함 + 압축 방식 (압축된 XMP 는 드물어서 지원하지 않음)
+    let header_len = XMP_PNG_KEYWORD.len() + 3;
+    if !contents.starts_with(XMP_PNG_KEYWORD) || contents.get(XMP_PNG_KEYWORD.len()..header_len)? != [0, 0, 0] {
+        return None;
+    }
+
+    // 언어\0, 번역된 키워드\0 건너뜀
+    let mut start = header_len;
+    for _ in 0..2 {
+        start += contents[start..].iter().position(|&b| b == 0)? + 1;
+    }
+    Some(contents.slice(start..))
+}
+
+fn read_webp(webp: &WebP) -> ImageMetadata {
+    let chunk_data = |id| webp.chunk_by_id(id).and_then(|chunk| chunk.content().data()).cloned();
+
+    // 규격은 TIFF 헤더부터지만 "Exif\0\0" 를 붙여 저장하는 프로그램도 있음
+    let exif = chunk_data(webp::CHUNK_EXIF).map(|exif| {
+        if exif.starts_with(EXIF_PREFIX) {
+            exif.slice(EXIF_PREFIX.len()..)
+        } else {
+            exif
+        }
+    });
+
+    ImageMetadata {
+        icc: chunk_data(webp::CHUNK_ICCP),
+        exif,
+        xmp: chunk_data(webp::CHUNK_XMP),
+        ..ImageMetadata::default()
+    }
+}
+
+// 투명도 사용 여부 (손실 + 알파는 VP8X/ALPH, 무손실은 VP8L 헤더의 alpha_is_used 비트)
+fn has_webp_alpha(webp: &WebP) -> bool {
+    if webp.has_chunk(webp::CHUNK_ALPH) {
+        return true;
+    }
+    if let Some(data) = webp.chunk_by_id(webp::CHUNK_VP8X).and_then(|chunk| chunk.content().data()) {
+        if data.first().is_some_and(|flags| flags & VP8X_ALPHA != 0) {
+            return true;
+        }
+    }
+    webp.chunk_by_id(webp::CHUNK_VP8L)
+        .and_then(|chunk| chunk.content().data())
+        .and_then(|data| data.get(1..5))
+        .is_some_and(|header| u32::from_le_bytes([header[0], header[1], header[2], header[3]]) >> 28 & 1 == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XMP: &[u8] = b"<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"/>";
+
+    fn itxt(keyword: &[u8], compressed: u8, language: &[u8], translated: &[u8], text: &[u8]) -> Bytes {
+        let mut contents = keyword.to_vec();
+        contents.extend_from_slice(&[0, compressed, 0]);
+        contents.extend_from_slice(language);
+        contents.push(0);
+        contents.extend_from_slice(translated);
+        contents.push(0);
+        contents.extend_from_slice(text);
+        Bytes::from(contents)
+    }
+
+    // IFD0 에 Orientation 항목 하나만 있는 리틀 엔디언 TIFF
+    fn tiff_with_orientation(value: u16) -> Vec<u8> {
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&1u16.to_le_bytes());
+        tiff.extend_from_slice(&0x0112u16.to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&[value as u8, 0, 0, 0]);
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff
+    }
+
+    #[test]
+    fn tiff_orientation_is_read_from_ifd0() {
+        let metadata = ImageMetadata::read(&tiff_with_orientation(6), MetadataPolicy::Strip);
+        assert_eq!(metadata.orientation, Orientation::Rotate90);
+        // TIFF 의 메타데이터는 옮기지 않음
+        assert!(ImageMetadata::read(&tiff_with_orientation(8), MetadataPolicy::KeepAll).is_empty());
+
+        let mut encoded = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::new_rgb8(2, 1).write_to(&mut encoded, ImageFormat::Tiff).unwrap();
+        assert_eq!(ImageMetadata::read(encoded.get_ref(), MetadataPolicy::KeepAll).orientation, Orientation::Normal);
+        assert_eq!(ImageMetadata::read(b"GIF89a", MetadataPolicy::KeepAll).orientation, Orientation::Normal);
+    }
+
+    #[test]
+    fn png_xmp_text_reads_uncompressed_body() {
+        assert_eq!(png_xmp_text(&itxt(XMP_PNG_KEYWORD, 0, b"", b"", XMP)).as_deref(), Some(XMP));
+        // 언어 / 번역된 키워드가 있어도 본문만
+        assert_eq!(png_xmp_text(&itxt(XMP_PNG_KEYWORD, 0, b"ko-KR", b"XMP", XMP)).as_deref(), Some(XMP));
+        assert_eq!(png_xmp_text(&itxt(XMP_PNG_KEYWORD, 0, b"", b"", b"")).as_deref(), Some(&b""[..]));
+    }
+
+    #[test]
+    fn png_xmp_text_rejects_other_chunks() {
+        assert_eq!(png_xmp_text(&itxt(XMP_PNG_KEYWORD, 1, b"", b"", XMP)), None);
+        assert_eq!(png_xmp_text(&itxt(b"Comment", 0, b"", b"", XMP)), None);
+        // 키워드가 XMP 키워드로 시작하기만 하는 경우
+        assert_eq!(png_xmp_text(&itxt(b"XML:com.adobe.xmp2", 0, b"", b"", XMP)), None);
+        // 언어 / 번역된 키워드의 끝 (\0) 이 없음
+        let mut truncated = XMP_PNG_KEYWORD.to_vec();
+        truncated.extend_from_slice(&[0, 0, 0, b'k', b'o']);
+        assert_eq!(png_xmp_text(&Bytes::from(truncated)), None);
+        assert_eq!(png_xmp_text(&Bytes::from_static(XMP_PNG_KEYWORD)), None);
+    }
+
+    #[test]
+    fn png_metadata_round_trips() {
+        let mut encoded = Vec::new();
+        image::DynamicImage::new_rgb8(4, 3)
+            .write_to(&mut std::io::Cursor::new(&mut encoded), ImageFormat::Png)
+            .unwrap();
+        let metadata = ImageMetadata {
+            exif: Some(Bytes::from_static(b"II\x2a\x00\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00")),
+            xmp: Some(Bytes::from_static(XMP)),
+            ..ImageMetadata::default()
+        };
+        let mut written = Vec::new();
+        metadata.write_into(encoded, ImageFormat::Png, &mut written).unwrap();
+
+        let read = ImageMetadata::read(&written, MetadataPolicy::KeepAll);
+        assert_eq!(read.xmp, metadata.xmp);
+        assert_eq!(read.exif, metadata.exif);
+        assert!(ImageMetadata::read(&written, MetadataPolicy::Strip).is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+// EXIF 방향 (Orientation 태그)
+//
+// 휴대폰 사진은 픽셀을 회전하지 않고 방향 태그만 붙여 저장하는 경우가 많아서
+// image 로 디코딩하면 옆으로 누운 이미지가 됩니다. 텍스트를 넣기 전에 태그대로 돌리고,
+// 결과 파일에 남기는 EXIF 의 태그는 1 (정방향) 로 바꾸고 너비 / 높이 태그와 썸네일도 맞춥니다.
+
+use image::DynamicImage;
+
+// IFD0 의 Orientation 태그 번호
+const ORIENTATION_TAG: u16 = 0x0112;
+// IFD0 에서 Exif IFD 위치를 가리키는 태그
+const EXIF_IFD_TAG: u16 = 0x8769;
+// Exif IFD 의 이미지 너비 / 높이
+const PIXEL_X_DIMENSION_TAG: u16 = 0xA002;
+const PIXEL_Y_DIMENSION_TAG: u16 = 0xA003;
+// TIFF SHORT 형식
+const TYPE_SHORT: u16 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    #[default]
+    Normal,
+    FlipHorizontal,
+    Rotate180,
+    FlipVertical,
+    // 90° 회전 후 좌우 반전
+    Rotate90FlipHorizontal,
+    Rotate90,
+    // 270° 회전 후 좌우 반전
+    Rotate270FlipHorizontal,
+    Rotate270,
+}
+
+impl Orientation {
+    // EXIF (TIFF 헤더부터) 에서 읽음, 없거나 잘못된 값이면 Normal
+    pub fn from_exif(exif: &[u8]) -> Self {
+        let value = find_orientation(exif).and_then(|(little_endian, offset)| read_u16(exif, offset, little_endian));
+        match value {
+            Some(2) => Orientation::FlipHorizontal,
+            Some(3) => Orientation::Rotate180,
+            Some(4) => Orientation::FlipVertical,
+            Some(5) => Orientation::Rotate90FlipHorizontal,
+            Some(6) => Orientation::Rotate90,
+            Some(7) => Orientation::Rotate270FlipHorizontal,
+            Some(8) => Orientation::Rotate270,
+            _ => Orientation::Normal,
+        }
+    }
+
+    // 가로 / 세로가 바뀌는 방향인지 (90 / 270 도)
+    pub fn transposes(self) -> bool {
+        matches!(
+            self,
+            Orientation::Rotate90FlipHorizontal
+                | Orientation::Rotate90
+                | Orientation::Rotate270FlipHorizontal
+                | Orientation::Rotate270
+        )
+    }
+
+    // 태그가 뜻하는 방향으로 픽셀을 돌림 (시계 방향)
+    pub fn apply(self, img: DynamicImage) -> DynamicImage {
+        match self {
+            Orientation::Normal => img,
+            Orientation::FlipHorizontal => img.fliph(),
+            Orientation::Rotate180 => img.rotate180(),
+            Orientation::FlipVertical => img.flipv(),
+            Orientation::Rotate90FlipHorizontal => img.rotate90().fliph(),
+            Orientation::Rotate90 => img.rotate90(),
+            Orientation::Rotate270FlipHorizontal => img.rotate270().fliph(),
+            Orientation::Rotate270 => img.rotate270(),
+        }
+    }
+}
+
+// 결과 파일에 남길 EXIF
+// Orientation 태그를 1 로 바꾸고, 90 / 270 도로 돌렸으면 Exif IFD 의 PixelXDimension / PixelYDimension 을 맞바꿈
+// IFD1 (썸네일) 은 원본 방향 그대로이고 텍스트도 없으므로 IFD0 에서 연결을 끊음
+pub fn normalize_exif(exif: &[u8], orientation: Orientation) -> Vec<u8> {
+    let mut normalized = exif.to_vec();
+    let Some((little_endian, ifd0)) = tiff_header(exif) else {
+        return normalized;
+    };
+
+    if let Some((_, offset)) = find_orientation(exif) {
+        let value = if little_endian { 1u16.to_le_bytes() } else { 1u16.to_be_bytes() };
+        normalized[offset..offset + 2].copy_from_slice(&value);
+    }
+
+    if orientation.transposes() {
+        let exif_ifd = find_entry(exif, ifd0, EXIF_IFD_TAG, little_endian)
+            .and_then(|entry| read_u32(exif, entry + 8, little_endian));
+        let dimensions = exif_ifd.and_then(|ifd| {
+            let x = find_entry(exif, ifd as usize, PIXEL_X_DIMENSION_TAG, little_endian)?;
+            let y = find_entry(exif, ifd as usize, PIXEL_Y_DIMENSION_TAG, little_endian)?;
+            Some((x, y))
+        });
+        if let Some((x, y)) = dimensions {
+            // 형식 (SHORT / LONG), 개수, 값을 통째로 맞바꿈
+            let x_fields = exif[x + 2..x + 12].to_vec();
+            normalized[x + 2..x + 12].copy_from_slice(&exif[y + 2..y + 12]);
+            normalized[y + 2..y + 12].copy_from_slice(&x_fields);
+        }
+    }
+
+    // IFD0 항목 다음 4바이트가 IFD1 위치
+    if let Some(entries) = read_u16(exif, ifd0, little_endian) {
+        let next_ifd = ifd0 + 2 + entries as usize * 12;
+        if let Some(next_ifd) = normalized.get_mut(next_ifd..next_ifd + 4) {
+            next_ifd.fill(0);
+        }
+    }
+    normalized
+}
+
+// (리틀 엔디언 여부, IFD0 위치)
+fn tiff_header(exif: &[u8]) -> Option<(bool, usize)> {
+    let little_endian = match exif.get(0..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    if read_u16(exif, 2, little_endian)? != 42 {
+        return None;
+    }
+    Some((little_endian, read_u32(exif, 4, little_endian)? as usize))
+}
+
+// ifd 에서 tag 항목의 위치
+// 항목은 12바이트: 태그(2), 형식(2), 개수(4), 값 또는 위치(4)
+fn find_entry(exif: &[u8], ifd: usize, tag: u16, little_endian: bool) -> Option<usize> {
+    let entries = read_u16(exif, ifd, little_endian)? as usize;
+    (0..entries)
+        .map(|i| ifd + 2 + i * 12)
+        .take_while(|&entry| entry + 12 <= exif.len())
+        .find(|&entry| read_u16(exif, entry, little_endian) == Some(tag))
+}
+
+// (리틀 엔디언 여부, IFD0 의 Orientation 값 위치)
+fn find_orientation(exif: &[u8]) -> Option<(bool, usize)> {
+    let (little_endian, ifd0) = tiff_header(exif)?;
+    let entry = find_entry(exif, ifd0, ORIENTATION_TAG, little_endian)?;
+    if read_u16(exif, entry + 2, little_endian)? != TYPE_SHORT {
+        return None;
+    }
+    // SHORT 하나는 항목 안에 바로 들어 있음
+    Some((little_endian, entry + 8))
+}
+
+fn read_u16(data: &[u8], offset: usize, little_endian: bool) -> Option<u16> {
+    let bytes = [*data.get(offset)?, *data.get(offset + 1)?];
+    Some(if little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+}
+
+fn read_u32(data: &[u8], offset: usize, little_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TYPE_LONG: u16 = 4;
+
+    // IFD0 (Orientation, Exif IFD 위치) → Exif IFD (PixelXDimension 4000 LONG, PixelYDimension 3000 SHORT) → 빈 IFD1
+    fn exif(little_endian: bool, orientation: u16) -> Vec<u8> {
+        let u16_bytes = |value: u16| if little_endian { value.to_le_bytes() } else { value.to_be_bytes() };
+        let u32_bytes = |value: u32| if little_endian { value.to_le_bytes() } else { value.to_be_bytes() };
+        let mut data = Vec::new();
+        let entry = |data: &mut Vec<u8>, tag: u16, kind: u16, value: u32| {
+            data.extend(u16_bytes(tag));
+            data.extend(u16_bytes(kind));
+            data.extend(u32_bytes(1));
+            if kind == TYPE_SHORT {
+                data.extend(u16_bytes(value as u16));
+                data.extend([0, 0]);
+            } else {
+                data.extend(u32_bytes(value));
+            }
+        };
+
+        data.extend(if little_endian { b"II" } else { b"MM" });
+        data.extend(u16_bytes(42));
+        data.extend(u32_bytes(8));
+        // IFD0 (8 ~ 38)
+        data.extend(u16_bytes(2));
+        entry(&mut data, ORIENTATION_TAG, TYPE_SHORT, orientation as u32);
+        entry(&mut data, EXIF_IFD_TAG, TYPE_LONG, 38);
+        data.extend(u32_bytes(68));
+        // Exif IFD (38 ~ 68)
+        data.extend(u16_bytes(2));
+        entry(&mut data, PIXEL_X_DIMENSION_TAG, TYPE_LONG, 4000);
+        entry(&mut data, PIXEL_Y_DIMENSION_TAG, TYPE_SHORT, 3000);
+        data.extend(u32_bytes(0));
+        // IFD1
+        data.extend(u16_bytes(0));
+        data.extend(u32_bytes(0));
+        data
+    }
+
+    // Exif IFD 의 (너비, 높이)
+    fn pixel_dimensions(exif: &[u8], little_endian: bool) -> (u32, u32) {
+        let read = |tag: u16| {
+            let entry = find_entry(exif, 38, tag, little_endian).unwrap();
+            match read_u16(exif, entry + 2, little_endian).unwrap() {
+                TYPE_SHORT => read_u16(exif, entry + 8, little_endian).unwrap() as u32,
+                _ => read_u32(exif, entry + 8, little_endian).unwrap(),
+            }
+        };
+        (read(PIXEL_X_DIMENSION_TAG), read(PIXEL_Y_DIMENSION_TAG))
+    }
+
+    #[test]
+    fn reads_all_orientations_in_both_byte_orders() {
+        let expected = [
+            Orientation::Normal,
+            Orientation::FlipHorizontal,
+            Orientation::Rotate180,
+            Orientation::FlipVertical,
+            Orientation::Rotate90FlipHorizontal,
+            Orientation::Rotate90,
+            Orientation::Rotate270FlipHorizontal,
+            Orientation::Rotate270,
+        ];
+        for little_endian in [true, false] {
+            for (value, orientation) in (1..=8).zip(expected) {
+                assert_eq!(Orientation::from_exif(&exif(little_endian, value)), orientation, "{} {}", little_endian, value);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_exif_is_normal() {
+        assert_eq!(Orientation::from_exif(&exif(true, 9)), Orientation::Normal);
+        assert_eq!(Orientation::from_exif(&exif(true, 0)), Orientation::Normal);
+        assert_eq!(Orientation::from_exif(b"XX\x2a\x00"), Orientation::Normal);
+        assert_eq!(Orientation::from_exif(&exif(false, 6)[..20]), Orientation::Normal);
+        assert_eq!(Orientation::from_exif(&[]), Orientation::Normal);
+    }
+
+    #[test]
+    fn find_orientation_points_at_value() {
+        // IFD0 (8) + 항목 수 (2) + 첫 항목의 값 위치 (8)
+        assert_eq!(find_orientation(&exif(true, 6)), Some((true, 18)));
+        assert_eq!(find_orientation(&exif(false, 6)), Some((false, 18)));
+        assert_eq!(find_orientation(b"MM\x00\x2a\x00\x00\x00\x08\x00\x00"), None);
+    }
+
+    #[test]
+    fn normalize_resets_orientation_and_swaps_dimensions_for_quarter_turns() {
+        for little_endian in [true, false] {
+            let normalized = normalize_exif(&exif(little_endian, 6), Orientation::Rotate90);
+            assert_eq!(Orientation::from_exif(&normalized), Orientation::Normal);
+            assert_eq!(pixel_dimensions(&normalized, little_endian), (3000, 4000));
+
+            let normalized = normalize_exif(&exif(little_endian, 3), Orientation::Rotate180);
+            assert_eq!(Orientation::from_exif(&normalized), Orientation::Normal);
+            assert_eq!(pixel_dimensions(&normalized, little_endian), (4000, 3000));
+        }
+    }
+
+    #[test]
+    fn normalize_unlinks_thumbnail_ifd() {
+        let normalized = normalize_exif(&exif(true, 1), Orientation::Normal);
+        assert_eq!(read_u32(&normalized, 34, true), Some(0));
+        assert_eq!(normalized.len(), exif(true, 1).len());
+    }
+
+    #[test]
+    fn transposes_only_quarter_turns() {
+        let transposed: Vec<u16> = (1..=8).filter(|&value| Orientation::from_exif(&exif(true, value)).transposes()).collect();
+        assert_eq!(transposed, [5, 6, 7, 8]);
+    }
+}
@@ -0,0 +1,478 @@
+// 결과 파일 저장 (임시 파일에 쓴 뒤 이름 변경 + 덮어쓰기 정책)
+//
+// 인코딩 도중 실패하거나 앱이 종료되어도 잘린 파일이 결과 폴더에 남지 않도록
+// 같은 폴더의 임시 파일에 끝까지 쓴 다음 최종 이름으로 바꿉니다.
+// 원본 파일 자체를 덮어쓰는 것은 정책과 관계없이 거부합니다.
+// 같은 이름인지는 유니코드 정규화 형태 (NFC / NFD) 와 관계없이 판단합니다.
+
+use serde::Deserialize;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::encode::EncodeOptions;
+use crate::metadata::MetadataPolicy;
+use crate::filename::{FileNameContext, FileNameTemplate};
+use crate::unicode;
+
+// auto-rename 에서 시도할 최대 번호
+const MAX_RENAME_ATTEMPTS: usize = 10_000;
+
+// 같은 이름을 고르고 이름을 바꾸는 사이에 다른 작업이 끼어들지 않도록 (병렬 일괄 처리)
+static TARGET_LOCK: Mutex<()> = Mutex::new(());
+// 임시 파일 이름 중복 방지
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverwritePolicy {
+    // 같은 이름의 파일이 있으면 덮어씀 (기존 동작)
+    #[default]
+    Overwrite,
+    // 같은 이름의 파일이 있으면 저장하지 않음
+    Skip,
+    // "name (1).jpg" 처럼 번호를 붙여 저장
+    #[serde(alias = "auto-rename")]
+    Rename,
+}
+
+// 결과 파일을 어디에 어떻게 쓸지 (일괄 처리에서는 BatchOptions 에 펼쳐서 받음)
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputOptions {
+    // 출력 폴더
+    pub output_path: String,
+    #[serde(default)]
+    pub overwrite: OverwritePolicy,
+    // 없으면 입력 파일명 그대로
+    #[serde(default)]
+    pub file_name_template: Option<FileNameTemplate>,
+    // 저장 형식과 품질 (없으면 입력 형식 그대로)
+    #[serde(default)]
+    pub encoding: EncodeOptions,
+    // 원본 EXIF / ICC / XMP 를 얼마나 남길지
+    #[serde(default)]
+    pub metadata: MetadataPolicy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveOutcome {
+    // 실제로 저장한 경로 (rename 이면 번호가 붙은 이름)
+    Written(PathBuf),
+    // 저장하지 않은 이유
+    Skipped(String),
+    // 저장하기 전에 취소됨 (일괄 처리)
+    Cancelled,
+}
+
+impl OutputOptions {
+    // 출력 폴더 (+ 하위 폴더) + 파일명 (템플릿이 없으면 입력 파일명, 저장 형식을 바꿨으면 확장자만 교체)
+    pub fn target_for(&self, context: &FileNameContext) -> Result<PathBuf, String> {
+        let dir = match context.subfolder {
+            Some(subfolder) => Path::new(&self.output_path).join(subfolder),
+            None => PathBuf::from(&self.output_path),
+        };
+        if let Some(template) = &self.file_name_template {
+            return Ok(dir.join(template.render(context)));
+        }
+
+        let input_filename = Path::new(context.image_path)
+            .file_name()
+            .ok_or_else(|| "파일명을 가져올 수 없습니다.".to_string())?;
+
+        let target = dir.join(input_filename);
+        Ok(match context.output_extension {
+            Some(extension) => target.with_extension(extension),
+            None => target,
+        })
+    }
+
+    // 파일명을 정하려면 이미지 크기가 필요한지 (디코딩 전에 덮어쓰기 확인을 할 수 있는지)
+    pub fn needs_dimensions(&self) -> bool {
+        self.file_name_template.as_ref().is_some_and(|template| template.needs_dimensions())
+    }
+}
+
+// 디코딩 전에 미리 확인 (원본과 같은 파일이면 오류, skip 인데 이미 있으면 건너뛸 이유)
+// 최종 결정은 save_atomic 에서 다시 함
+pub fn precheck(image_path: &str, target: &Path, policy: OverwritePolicy) -> Result<Option<String>, String> {
+    let Some(existing) = DirNames::default().existing(target) else {
+        return Ok(None);
+    };
+    match policy {
+        OverwritePolicy::Overwrite => {
+            ensure_not_input(image_path, &existing)?;
+            Ok(None)
+        }
+        OverwritePolicy::Skip => Ok(Some(exists_reason(&existing))),
+        OverwritePolicy::Rename => Ok(None),
+    }
+}
+
+// encode 로 임시 파일에 쓴 뒤 정책에 따라 최종 이름을 정해 바꿈
+pub fn save_atomic(
+    image_path: &str,
+    target: &Path,
+    policy: OverwritePolicy,
+    encode: impl FnOnce(&mut BufWriter<File>) -> Result<(), String>,
+) -> Result<SaveOutcome, String> {
+    let dir = parent_dir(target);
+    if !dir.exists() {
+        fs::create_dir_all(dir).map_err(|e| format!("출력 디렉토리 생성 실패: {}", e))?;
+    }
+
+    let temp_path = temp_file_path(dir, target);
+    if let Err(e) = write_temp(&temp_path, encode) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    let result = {
+        let _guard = TARGET_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        match final_target(image_path, target, policy) {
+            Ok(Some(path)) => fs::rename(&temp_path, &path)
+                .map(|_| SaveOutcome::Written(path))
+                .map_err(|e| format!("파일 저장 실패: {}", e)),
+            Ok(None) => Ok(SaveOutcome::Skipped(exists_reason(target))),
+            Err(e) => Err(e),
+        }
+    };
+
+    // 이름을 바꾸지 못했으면 임시 파일 정리
+    if !matches!(result, Ok(SaveOutcome::Written(_))) {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_temp(temp_path: &Path, encode: impl FnOnce(&mut BufWriter<File>) -> Result<(), String>) -> Result<(), String> {
+    let file = File::create(temp_path).map_err(|e| format!("파일 생성 실패: {}", e))?;
+    let mut writer = BufWriter::new(file);
+    encode(&mut writer)?;
+    writer.flush().map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+    // 이름을 바꾼 뒤에 내용이 비어 있지 않도록 디스크에 반영
+    writer
+        .get_ref()
+        .sync_all()
+        .map_err(|e| format!("파일 쓰기 실패: {}", e))
+}
+
+// 정책에 따라 실제로 쓸 경로 (None 이면 건너뜀)
+// 덮어쓸 때는 기존 파일의 이름 그대로 씀 (정규화 형태만 다른 파일이 둘 생기지 않도록)
+fn final_target(image_path: &str, target: &Path, policy: OverwritePolicy) -> Result<Option<PathBuf>, String> {
+    let mut names = DirNames::default();
+    let Some(existing) = names.existing(target) else {
+        return Ok(Some(target.to_path_buf()));
+    };
+    match policy {
+        OverwritePolicy::Overwrite => {
+            ensure_not_input(image_path, &existing)?;
+            Ok(Some(existing))
+        }
+        OverwritePolicy::Skip => Ok(None),
+        OverwritePolicy::Rename => numbered_path(target, &mut names).map(Some),
+    }
+}
+
+// 일괄 처리에서 각 이미지가 쓰기로 한 결과 경로 (NFC 로 합친 경로 → 원본 이미지 경로)
+// overwrite 정책에서 템플릿에 {index} 가 없으면 여러 이미지가 같은 파일을 차례로 덮어써
+// 마지막 이미지만 남으므로, 먼저 저장하는 이미지만 저장하고 나머지는 실패로 보고
+#[derive(Default)]
+pub struct ClaimedTargets(Mutex<HashMap<String, String>>);
+
+impl ClaimedTargets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 저장하기 직전에 차지 (저장에 실패하면 TargetClaim 을 버릴 때 놓아 줌)
+    pub fn claim(&self, target: &Path, image_path: &str) -> Result<TargetClaim<'_>, String> {
+        let key = unicode::to_nfc(&target.to_string_lossy()).into_owned();
+        let mut claimed = self.lock();
+        match claimed.entry(key.clone()) {
+            Entry::Occupied(entry) => Err(format!(
+                "다른 이미지({})와 결과 파일명이 같아 저장하지 않습니다: {} (파일명 템플릿에 {{index}} 를 넣거나 번호 붙여 저장을 선택하세요)",
+                entry.get(),
+                target.display()
+            )),
+            Entry::Vacant(entry) => {
+                entry.insert(image_path.to_string());
+                Ok(TargetClaim { targets: self, key: Some(key) })
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// 차지한 결과 경로 (keep 하지 않고 버리면 다른 이미지가 다시 쓸 수 있음)
+pub struct TargetClaim<'a> {
+    targets: &'a ClaimedTargets,
+    key: Option<String>,
+}
+
+impl TargetClaim<'_> {
+    // 저장에 성공했으면 일괄 처리가 끝날 때까지 유지
+    pub fn keep(mut self) {
+        self.key = None;
+    }
+}
+
+impl Drop for TargetClaim<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.targets.lock().remove(&key);
+        }
+    }
+}
+
+// "name (1).ext", "name (2).ext", ... 중 비어 있는 첫 이름
+fn numbered_path(target: &Path, names: &mut DirNames) -> Result<PathBuf, String> {
+    let stem = target.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    let extension = target.extension().map(|ext| ext.to_string_lossy());
+
+    for n in 1..=MAX_RENAME_ATTEMPTS {
+        let name = match &extension {
+            Some(ext) => format!("{} ({}).{}", stem, n, ext),
+            None => format!("{} ({})", stem, n),
+        };
+        let candidate = target.with_file_name(name);
+        if names.existing(&candidate).is_none() {
+            return Ok(candidate);
+        }
+    }
+    Err(format!("사용할 수 있는 파일명이 없습니다: {}", target.display()))
+}
+
+// 폴더 안의 파일명 (NFC 로 합친 이름 → 실제 이름)
+// rename 에서 번호를 바꿔 가며 찾을 때 후보마다 폴더를 다시 읽지 않도록 처음 필요할 때 한 번만 읽음
+#[derive(Default)]
+struct DirNames(Option<HashMap<String, OsString>>);
+
+impl DirNames {
+    // target 또는 정규화 형태만 다른 이름으로 이미 있는 파일 (macOS 에서 복사해 온 NFD 파일명 등)
+    // ASCII 이름은 정규화해도 같으므로 폴더를 읽지 않음
+    fn existing(&mut self, target: &Path) -> Option<PathBuf> {
+        if target.exists() {
+            return Some(target.to_path_buf());
+        }
+        let name = target.file_name()?.to_str().filter(|name| !name.is_ascii())?;
+        let names = self.0.get_or_insert_with(|| read_names(parent_dir(target)));
+        names.get(&*unicode::to_nfc(name)).map(|actual| target.with_file_name(actual))
+    }
+}
+
+// UTF-8 이 아닌 이름은 정규화할 수 없으므로 제외 (그대로 같은 이름이면 exists 로 찾음)
+fn read_names(dir: &Path) -> HashMap<String, OsString> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let key = unicode::to_nfc(name.to_str()?).into_owned();
+            Some((key, name))
+        })
+        .collect()
+}
+
+fn parent_dir(target: &Path) -> &Path {
+    target.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."))
+}
+
+// 출력 경로가 원본 파일을 가리키면 (출력 폴더 = 입력 폴더, 심볼릭 링크 등) 거부
+fn ensure_not_input(image_path: &str, target: &Path) -> Result<(), String> {
+    let same_file = match (fs::canonicalize(image_path), fs::canonicalize(target)) {
+        (Ok(input), Ok(output)) => input == output,
+        _ => false,
+    };
+    if same_file {
+        return Err(format!("출력 파일이 원본 파일과 같아 저장하지 않습니다: {}", target.display()));
+    }
+    Ok(())
+}
+
+fn exists_reason(target: &Path) -> String {
+    format!("같은 이름의 파일이 이미 있습니다: {}", target.display())
+}
+
+// 같은 폴더 안의 숨김 임시 파일 (다른 파일 시스템으로 옮기면 이름 변경이 원자적이지 않음)
+fn temp_file_path(dir: &Path, target: &Path) -> PathBuf {
+    let name = target.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let counter = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    dir.join(format!(".{}.{}-{}.tmp", name, std::process::id(), counter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_normalization::UnicodeNormalization;
+
+    // 파일 시스템에 따라 (macOS) 정규화 형태와 관계없이 찾아지기도 하므로 NFC 이름으로 비교
+    fn nfc_name(path: Option<PathBuf>) -> Option<String> {
+        path.map(|path| path.file_name().unwrap().to_string_lossy().nfc().collect())
+    }
+
+    // 테스트마다 비어 있는 임시 폴더
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("image-overlay-output-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn dir_names_match_nfd_file_for_nfc_target() {
+        let dir = temp_dir("nfd");
+        let nfd: String = "한글.jpg".nfd().collect();
+        fs::write(dir.join(&nfd), b"x").unwrap();
+
+        let mut names = DirNames::default();
+        assert_eq!(nfc_name(names.existing(&dir.join("한글.jpg"))).as_deref(), Some("한글.jpg"));
+        assert_eq!(names.existing(&dir.join("다른.jpg")), None);
+        assert_eq!(names.existing(&dir.join("a.jpg")), None);
+    }
+
+    #[test]
+    fn dir_names_match_nfc_file_for_nfd_target() {
+        let dir = temp_dir("nfc");
+        fs::write(dir.join("사진.png"), b"x").unwrap();
+        let nfd: String = "사진.png".nfd().collect();
+        assert_eq!(nfc_name(DirNames::default().existing(&dir.join(nfd))).as_deref(), Some("사진.png"));
+    }
+
+    #[test]
+    fn numbered_path_skips_names_taken_in_either_form() {
+        let dir = temp_dir("numbered");
+        fs::write(dir.join("한글.jpg"), b"x").unwrap();
+        let nfd: String = "한글 (1).jpg".nfd().collect();
+        fs::write(dir.join(nfd), b"x").unwrap();
+
+        let mut names = DirNames::default();
+        assert_eq!(numbered_path(&dir.join("한글.jpg"), &mut names), Ok(dir.join("한글 (2).jpg")));
+    }
+
+    #[test]
+    fn claimed_target_is_released_unless_kept() {
+        let claimed = ClaimedTargets::new();
+        let target = Path::new("out/같은.jpg");
+        let claim = claimed.claim(target, "a.jpg").unwrap();
+        let error = claimed.claim(target, "b.jpg").err().unwrap();
+        assert!(error.contains("a.jpg"), "{}", error);
+
+        // 저장하지 못한 작업 (버림) → 다음 작업이 차지할 수 있음
+        drop(claim);
+        claimed.claim(target, "b.jpg").unwrap().keep();
+        assert!(claimed.claim(target, "c.jpg").is_err());
+
+        // NFD 로 된 같은 경로도 겹침
+        let nfd: String = target.to_string_lossy().nfd().collect();
+        assert!(claimed.claim(Path::new(&nfd), "d.jpg").is_err());
+    }
+
+    fn write_bytes(bytes: &'static [u8]) -> impl FnOnce(&mut BufWriter<File>) -> Result<(), String> {
+        move |writer| writer.write_all(bytes).map_err(|e| e.to_string())
+    }
+
+    // 임시 파일 (".<이름>.<pid>-<번호>.tmp") 이 남지 않았는지
+    fn leftover_temp_files(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter_map(Result::ok)
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect()
+    }
+
+    #[test]
+    fn save_atomic_writes_through_temp_file() {
+        let dir = temp_dir("atomic");
+        let target = dir.join("nested").join("out.jpg");
+        let outcome = save_atomic("input.jpg", &target, OverwritePolicy::Overwrite, write_bytes(b"new")).unwrap();
+        assert_eq!(outcome, SaveOutcome::Written(target.clone()));
+        assert_eq!(fs::read(&target).unwrap(), b"new");
+        assert!(leftover_temp_files(&dir.join("nested")).is_empty());
+    }
+
+    #[test]
+    fn save_atomic_removes_temp_file_when_encoding_fails() {
+        let dir = temp_dir("encode-error");
+        let target = dir.join("out.jpg");
+        fs::write(&target, b"old").unwrap();
+
+        let result = save_atomic("input.jpg", &target, OverwritePolicy::Overwrite, |writer| {
+            writer.write_all(b"partial").unwrap();
+            Err("인코딩 실패".to_string())
+        });
+        assert_eq!(result, Err("인코딩 실패".to_string()));
+        // 기존 파일은 그대로, 잘린 임시 파일은 남지 않음
+        assert_eq!(fs::read(&target).unwrap(), b"old");
+        assert!(leftover_temp_files(&dir).is_empty());
+    }
+
+    #[test]
+    fn overwrite_policy_skip_keeps_existing_file() {
+        let dir = temp_dir("skip");
+        let target = dir.join("out.jpg");
+        fs::write(&target, b"old").unwrap();
+
+        assert!(precheck("input.jpg", &target, OverwritePolicy::Skip).unwrap().is_some());
+        let outcome = save_atomic("input.jpg", &target, OverwritePolicy::Skip, write_bytes(b"new")).unwrap();
+        assert!(matches!(outcome, SaveOutcome::Skipped(_)));
+        assert_eq!(fs::read(&target).unwrap(), b"old");
+        assert!(leftover_temp_files(&dir).is_empty());
+    }
+
+    #[test]
+    fn overwrite_policy_rename_numbers_new_file() {
+        let dir = temp_dir("rename");
+        let target = dir.join("out.jpg");
+        fs::write(&target, b"old").unwrap();
+
+        assert_eq!(precheck("input.jpg", &target, OverwritePolicy::Rename), Ok(None));
+        let first = save_atomic("input.jpg", &target, OverwritePolicy::Rename, write_bytes(b"1")).unwrap();
+        let second = save_atomic("input.jpg", &target, OverwritePolicy::Rename, write_bytes(b"2")).unwrap();
+        assert_eq!(first, SaveOutcome::Written(dir.join("out (1).jpg")));
+        assert_eq!(second, SaveOutcome::Written(dir.join("out (2).jpg")));
+        assert_eq!(fs::read(&target).unwrap(), b"old");
+
+        let mut names = DirNames::default();
+        assert_eq!(numbered_path(&dir.join("no-extension"), &mut names), Ok(dir.join("no-extension (1)")));
+    }
+
+    #[test]
+    fn overwrite_replaces_existing_file() {
+        let dir = temp_dir("overwrite");
+        let target = dir.join("out.jpg");
+        fs::write(&target, b"old").unwrap();
+        let outcome = save_atomic("input.jpg", &target, OverwritePolicy::Overwrite, write_bytes(b"new")).unwrap();
+        assert_eq!(outcome, SaveOutcome::Written(target.clone()));
+        assert_eq!(fs::read(&target).unwrap(), b"new");
+    }
+
+    #[test]
+    fn refuses_to_overwrite_input_file() {
+        let dir = temp_dir("input");
+        let input = dir.join("photo.jpg");
+        fs::write(&input, b"original").unwrap();
+        let input_path = input.to_string_lossy().into_owned();
+
+        assert!(ensure_not_input(&input_path, &input).is_err());
+        assert!(ensure_not_input(&input_path, &dir.join("other.jpg")).is_ok());
+        assert!(precheck(&input_path, &input, OverwritePolicy::Overwrite).is_err());
+        // 같은 파일을 다른 경로 ("./") 로 가리켜도 거부
+        let same_file = dir.join(".").join("photo.jpg");
+        let result = save_atomic(&input_path, &same_file, OverwritePolicy::Overwrite, write_bytes(b"labeled"));
+        assert!(result.is_err());
+        assert_eq!(fs::read(&input).unwrap(), b"original");
+        assert!(leftover_temp_files(&dir).is_empty());
+    }
+}
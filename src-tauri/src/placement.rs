@@ -0,0 +1,223 @@
+// 텍스트 배치 위치 (9방향 기준점 + 여백)
+//
+// 측정된 텍스트 영역으로 그리기 원점을 계산하므로, 여백을 퍼센트로 지정하면
+// 해상도가 다른 이미지에서도 같은 비율의 위치에 놓입니다.
+
+use serde::Deserialize;
+
+use crate::layout::TextBounds;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Anchor {
+    TopLeft,
+    #[serde(alias = "top-center")]
+    Top,
+    TopRight,
+    #[serde(alias = "center-left")]
+    Left,
+    Center,
+    #[serde(alias = "center-right")]
+    Right,
+    BottomLeft,
+    #[serde(alias = "bottom-center")]
+    Bottom,
+    #[default]
+    BottomRight,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MarginUnit {
+    #[default]
+    Px,
+    // 가로 여백은 이미지 너비, 세로 여백은 이미지 높이 기준
+    Percent,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlacementOptions {
+    pub anchor: Anchor,
+    // 기준점 쪽 이미지 가장자리에서 텍스트 영역까지의 거리 (가운데 정렬 축에서는 무시)
+    pub margin_x: f32,
+    pub margin_y: f32,
+    pub margin_unit: MarginUnit,
+}
+
+impl PlacementOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        let max = match self.margin_unit {
+            MarginUnit::Px => f32::MAX,
+            MarginUnit::Percent => 100.0,
+        };
+        for margin in [self.margin_x, self.margin_y] {
+            if !(margin.is_finite() && (0.0..=max).contains(&margin)) {
+                return Err(format!("여백 값이 유효하지 않습니다: {}", margin));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 텍스트를 놓을 위치: 기존 절대 좌표 또는 기준점 배치
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextPosition {
+    // 그리기 원점 (draw_text_mut 의 x, y)
+    Absolute { x: f32, y: f32 },
+    Anchored(PlacementOptions),
+}
+
+impl TextPosition {
+    // placement 가 있으면 position_x/position_y 대신 사용
+    pub fn from_args(position_x: f32, position_y: f32, placement: Option<PlacementOptions>) -> Result<Self, String> {
+        match placement {
+            Some(placement) => {
+                placement.validate()?;
+                Ok(TextPosition::Anchored(placement))
+            }
+            None => {
+                if position_x < 0.0 || position_y < 0.0 {
+                    return Err("위치 좌표가 유효하지 않습니다.".to_string());
+                }
+                Ok(TextPosition::Absolute { x: position_x, y: position_y })
+            }
+        }
+    }
+
+    // 미리보기용으로 이미지를 줄였을 때 (퍼센트 여백은 그대로)
+    pub fn scaled(&self, factor: f32) -> Self {
+        match *self {
+            TextPosition::Absolute { x, y } => TextPosition::Absolute { x: x * factor, y: y * factor },
+            TextPosition::Anchored(placement) if placement.margin_unit == MarginUnit::Px => TextPosition::Anchored(PlacementOptions {
+                margin_x: placement.margin_x * factor,
+                margin_y: placement.margin_y * factor,
+                ..placement
+            }),
+            anchored => anchored,
+        }
+    }
+
+    // 측정된 텍스트 영역(bounds)을 기준으로 그리기 원점 계산
+    pub fn origin(&self, img_width: u32, img_height: u32, bounds: &TextBounds) -> (i32, i32) {
+        let placement = match *self {
+            TextPosition::Absolute { x, y } => return (x as i32, y as i32),
+            TextPosition::Anchored(placement) => placement,
+        };
+
+        let (margin_x, margin_y) = match placement.margin_unit {
+            MarginUnit::Px => (placement.margin_x, placement.margin_y),
+            MarginUnit::Percent => (
+                img_width as f32 * placement.margin_x / 100.0,
+                img_height as f32 * placement.margin_y / 100.0,
+            ),
+        };
+
+        let (align_x, align_y) = placement.anchor.axes();
+
+        let x = resolve_axis(align_x, margin_x, img_width, bounds.width) - bounds.x;
+        let y = resolve_axis(align_y, margin_y, img_height, bounds.height) - bounds.y;
+        (x, y)
+    }
+}
+
+// 한 축에서의 정렬 (왼쪽/위, 가운데, 오른쪽/아래)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AxisAlign {
+    Start,
+    Center,
+    End,
+}
+
+impl Anchor {
+    fn axes(self) -> (AxisAlign, AxisAlign) {
+        use AxisAlign::*;
+        match self {
+            Anchor::TopLeft => (Start, Start),
+            Anchor::Top => (Center, Start),
+            Anchor::TopRight => (End, Start),
+            Anchor::Left => (Start, Center),
+            Anchor::Center => (Center, Center),
+            Anchor::Right => (End, Center),
+            Anchor::BottomLeft => (Start, End),
+            Anchor::Bottom => (Center, End),
+            Anchor::BottomRight => (End, End),
+        }
+    }
+}
+
+// 한 축에서 텍스트 영역의 시작 위치
+fn resolve_axis(align: AxisAlign, margin: f32, limit: u32, extent: u32) -> i32 {
+    let free = limit as f32 - extent as f32;
+    let start = match align {
+        AxisAlign::Start => margin,
+        AxisAlign::Center => free / 2.0,
+        AxisAlign::End => free - margin,
+    };
+    start.round() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchored(anchor: Anchor, margin_x: f32, margin_y: f32, margin_unit: MarginUnit) -> TextPosition {
+        TextPosition::Anchored(PlacementOptions { anchor, margin_x, margin_y, margin_unit })
+    }
+
+    #[test]
+    fn origin_for_every_anchor() {
+        // 1000x500 이미지, 원점에서 (-2, -10) 만큼 벗어난 200x100 텍스트 영역
+        let bounds = TextBounds { x: -2, y: -10, width: 200, height: 100 };
+        // (기준점, px 여백 20/10 일 때 영역 시작, 퍼센트 여백 5%/4% (= 50/20px) 일 때 영역 시작)
+        let cases = [
+            (Anchor::TopLeft, (20, 10), (50, 20)),
+            (Anchor::Top, (400, 10), (400, 20)),
+            (Anchor::TopRight, (780, 10), (750, 20)),
+            (Anchor::Left, (20, 200), (50, 200)),
+            (Anchor::Center, (400, 200), (400, 200)),
+            (Anchor::Right, (780, 200), (750, 200)),
+            (Anchor::BottomLeft, (20, 390), (50, 380)),
+            (Anchor::Bottom, (400, 390), (400, 380)),
+            (Anchor::BottomRight, (780, 390), (750, 380)),
+        ];
+
+        for (anchor, px, percent) in cases {
+            // 그리기 원점 = 영역 시작 - bounds 의 x/y
+            let px_origin = anchored(anchor, 20.0, 10.0, MarginUnit::Px).origin(1000, 500, &bounds);
+            assert_eq!(px_origin, (px.0 + 2, px.1 + 10), "{:?} px", anchor);
+            let percent_origin = anchored(anchor, 5.0, 4.0, MarginUnit::Percent).origin(1000, 500, &bounds);
+            assert_eq!(percent_origin, (percent.0 + 2, percent.1 + 10), "{:?} percent", anchor);
+        }
+    }
+
+    #[test]
+    fn scaled_changes_only_pixel_values() {
+        let bounds = TextBounds { x: 0, y: 0, width: 100, height: 50 };
+
+        let px = anchored(Anchor::BottomRight, 40.0, 20.0, MarginUnit::Px);
+        assert_eq!(px.scaled(0.5), anchored(Anchor::BottomRight, 20.0, 10.0, MarginUnit::Px));
+        // 절반 크기 이미지에서 절반 크기 텍스트는 원래 위치의 절반
+        let full = px.origin(2000, 1000, &TextBounds { width: 200, height: 100, ..bounds });
+        let half = px.scaled(0.5).origin(1000, 500, &bounds);
+        assert_eq!((full.0 / 2, full.1 / 2), half);
+
+        let percent = anchored(Anchor::TopLeft, 5.0, 5.0, MarginUnit::Percent);
+        assert_eq!(percent.scaled(0.5), percent);
+
+        let absolute = TextPosition::Absolute { x: 30.0, y: 12.0 };
+        assert_eq!(absolute.scaled(0.5), TextPosition::Absolute { x: 15.0, y: 6.0 });
+        assert_eq!(absolute.origin(1000, 500, &bounds), (30, 12));
+    }
+
+    #[test]
+    fn rejects_invalid_margins() {
+        let placement = |margin_x: f32, margin_unit| Some(PlacementOptions { margin_x, margin_unit, ..Default::default() });
+        assert!(TextPosition::from_args(0.0, 0.0, placement(500.0, MarginUnit::Px)).is_ok());
+        assert!(TextPosition::from_args(0.0, 0.0, placement(100.0, MarginUnit::Percent)).is_ok());
+        assert!(TextPosition::from_args(0.0, 0.0, placement(101.0, MarginUnit::Percent)).is_err());
+        assert!(TextPosition::from_args(0.0, 0.0, placement(-1.0, MarginUnit::Px)).is_err());
+        assert!(TextPosition::from_args(0.0, 0.0, placement(f32::NAN, MarginUnit::Px)).is_err());
+        assert!(TextPosition::from_args(-1.0, 0.0, None).is_err());
+    }
+}
@@ -0,0 +1,382 @@
+// 오버레이 합성 (source-over 알파 블렌딩)
+//
+// draw_filled_rect_mut / put_pixel 은 픽셀을 그대로 덮어써서 반투명 배경이 RGB 이미지에서는
+// 불투명하게 보이고 PNG 에는 알파값이 그대로 기록됩니다. 여기서는 원본 이미지 형식
+// (그레이스케일, RGB, 알파 채널 유무, 8/16비트, f32)을 유지한 채 그 위에 합성합니다.
+
+use image::{DynamicImage, GenericImage, GenericImageView, Pixel, Rgba, RgbaImage};
+use imageproc::rect::Rect;
+use rusttype::PositionedGlyph;
+
+use crate::effects::CoverageMask;
+use crate::style::TextStyle;
+
+// 그레이스케일 변환 계수 (image 크레이트와 동일한 Rec. 709)
+const LUMA_WEIGHTS: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+// 0.0 ~ 1.0 으로 정규화한 오버레이 색상
+#[derive(Debug, Clone, Copy)]
+struct Source {
+    rgb: [f32; 3],
+    luma: f32,
+    alpha: f32,
+}
+
+impl Source {
+    fn new(color: Rgba<u8>) -> Self {
+        let [r, g, b, a] = color.0;
+        let rgb = [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0];
+        Source {
+            rgb,
+            luma: rgb.iter().zip(LUMA_WEIGHTS).map(|(c, w)| c * w).sum(),
+            alpha: a as f32 / 255.0,
+        }
+    }
+}
+
+// 채널 값 <-> 0.0 ~ 1.0 변환
+trait Channel: Copy {
+    fn to_unit(self) -> f32;
+    fn from_unit(value: f32) -> Self;
+}
+
+impl Channel for u8 {
+    fn to_unit(self) -> f32 {
+        self as f32 / 255.0
+    }
+
+    fn from_unit(value: f32) -> Self {
+        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+}
+
+impl Channel for u16 {
+    fn to_unit(self) -> f32 {
+        self as f32 / 65535.0
+    }
+
+    fn from_unit(value: f32) -> Self {
+        (value.clamp(0.0, 1.0) * 65535.0).round() as u16
+    }
+}
+
+impl Channel for f32 {
+    fn to_unit(self) -> f32 {
+        self
+    }
+
+    fn from_unit(value: f32) -> Self {
+        value
+    }
+}
+
+// 픽셀 한 개에 source-over 합성 (채널 수로 Y / YA / RGB / RGBA 구분)
+fn composite<T: Channel>(channels: &mut [T], source: &Source, coverage: f32) {
+    let src_alpha = source.alpha * coverage.clamp(0.0, 1.0);
+    if src_alpha <= 0.0 {
+        return;
+    }
+
+    let (color_channels, has_alpha) = match channels.len() {
+        1 => (1, false),
+        2 => (1, true),
+        3 => (3, false),
+        _ => (3, true),
+    };
+
+    let dst_alpha = if has_alpha { channels[color_channels].to_unit() } else { 1.0 };
+    let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+    if out_alpha <= 0.0 {
+        return;
+    }
+
+    for (i, channel) in channels.iter_mut().take(color_channels).enumerate() {
+        let src = if color_channels == 1 { source.luma } else { source.rgb[i] };
+        let dst = channel.to_unit();
+        *channel = T::from_unit((src * src_alpha + dst * dst_alpha * (1.0 - src_alpha)) / out_alpha);
+    }
+
+    if has_alpha {
+        channels[color_channels] = T::from_unit(out_alpha);
+    }
+}
+
+// 이미지 형식별로 원본 정밀도를 유지하며 합성 (범위 밖 좌표는 호출하는 쪽에서 걸러야 함)
+fn blend_pixel(img: &mut DynamicImage, x: u32, y: u32, source: &Source, coverage: f32) {
+    match img {
+        DynamicImage::ImageLuma8(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageLumaA8(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageRgb8(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageRgba8(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageLuma16(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageLumaA16(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageRgb16(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageRgba16(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageRgb32F(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        DynamicImage::ImageRgba32F(buf) => composite(buf.get_pixel_mut(x, y).channels_mut(), source, coverage),
+        // 새로 추가되는 형식은 8비트 RGBA 로 변환해서 합성
+        _ => {
+            let mut pixel = img.get_pixel(x, y);
+            composite(pixel.channels_mut(), source, coverage);
+            img.put_pixel(x, y, pixel);
+        }
+    }
+}
+
+// 사각형 영역을 color 로 합성 (이미지 밖은 잘라냄)
+pub fn fill_rect(img: &mut DynamicImage, rect: Rect, color: Rgba<u8>) {
+    let (img_width, img_height) = img.dimensions();
+    let source = Source::new(color);
+
+    let left = rect.left().max(0);
+    let top = rect.top().max(0);
+    let right = rect.right().min(img_width as i32 - 1);
+    let bottom = rect.bottom().min(img_height as i32 - 1);
+
+    for y in top..=bottom {
+        for x in left..=right {
+            blend_pixel(img, x as u32, y as u32, &source, 1.0);
+        }
+    }
+}
+
+// 커버리지 마스크를 (x, y) 기준으로 color 합성
+pub fn fill_mask(img: &mut DynamicImage, mask: &CoverageMask, color: Rgba<u8>, x: i32, y: i32) {
+    let (img_width, img_height) = img.dimensions();
+    let source = Source::new(color);
+
+    for (mx, my, value) in mask.image.enumerate_pixels() {
+        let image_x = x + mask.left + mx as i32;
+        let image_y = y + mask.top + my as i32;
+
+        if value.0[0] > 0 && (0..img_width as i32).contains(&image_x) && (0..img_height as i32).contains(&image_y) {
+            blend_pixel(img, image_x as u32, image_y as u32, &source, value.0[0] as f32 / 255.0);
+        }
+    }
+}
+
+// 스타일에 지정된 효과와 함께 텍스트 그리기 (그림자 → 글로우 → 외곽선 → 글자 순서)
+pub fn draw_text(img: &mut DynamicImage, style: &TextStyle, x: i32, y: i32, glyphs: &[PositionedGlyph]) {
+    if style.has_effects() {
+        if let Some(mask) = CoverageMask::from_glyphs(glyphs, style.effect_spread() as u32) {
+            // 그림자와 글로우는 외곽선까지 포함한 모양을 따라감
+            let outline = mask.dilate(style.stroke_width());
+
+            if let Some(shadow) = style.shadow {
+                fill_mask(img, &outline.blur(shadow.blur), shadow.color, x + shadow.offset_x, y + shadow.offset_y);
+            }
+
+            if let Some(glow) = style.glow {
+                fill_mask(img, &outline.dilate(glow.radius / 4.0).blur(glow.radius), glow.color, x, y);
+            }
+
+            if let Some(stroke) = style.stroke {
+                fill_mask(img, &outline, stroke.color, x, y);
+            }
+        }
+    }
+
+    draw_glyphs(img, style.text_color, x, y, glyphs);
+}
+
+// 배치된 글리프를 (x, y) 기준으로 합성 (글리프 커버리지를 알파에 곱함)
+pub fn draw_glyphs(img: &mut DynamicImage, color: Rgba<u8>, x: i32, y: i32, glyphs: &[PositionedGlyph]) {
+    let (img_width, img_height) = img.dimensions();
+    let source = Source::new(color);
+
+    for glyph in glyphs {
+        let bb = match glyph.pixel_bounding_box() {
+            Some(bb) => bb,
+            None => continue,
+        };
+
+        glyph.draw(|gx, gy, coverage| {
+            let image_x = x + bb.min.x + gx as i32;
+            let image_y = y + bb.min.y + gy as i32;
+
+            if (0..img_width as i32).contains(&image_x) && (0..img_height as i32).contains(&image_y) {
+                blend_pixel(img, image_x as u32, image_y as u32, &source, coverage);
+            }
+        });
+    }
+}
+
+// RGBA 이미지를 (x, y) 에 합성 (픽셀 알파에 opacity 를 곱함, 이미지 밖은 잘라냄)
+pub fn draw_image(img: &mut DynamicImage, overlay: &RgbaImage, x: i32, y: i32, opacity: f32) {
+    let (img_width, img_height) = img.dimensions();
+
+    for (ox, oy, pixel) in overlay.enumerate_pixels() {
+        let image_x = x + ox as i32;
+        let image_y = y + oy as i32;
+
+        if pixel.0[3] > 0 && (0..img_width as i32).contains(&image_x) && (0..img_height as i32).contains(&image_y) {
+            blend_pixel(img, image_x as u32, image_y as u32, &Source::new(*pixel), opacity);
+        }
+    }
+}
+
+// area 안의 픽셀마다 pattern 이 돌려준 색상을 opacity 를 곱해 합성 (반복 워터마크, 회전한 텍스트, 이미지 밖은 잘라냄)
+pub fn fill_pattern(img: &mut DynamicImage, area: Rect, opacity: f32, pattern: impl Fn(u32, u32) -> Option<Rgba<u8>>) {
+    let (img_width, img_height) = img.dimensions();
+
+    let left = area.left().max(0);
+    let top = area.top().max(0);
+    let right = area.right().min(img_width as i32 - 1);
+    let bottom = area.bottom().min(img_height as i32 - 1);
+
+    for y in top..=bottom {
+        for x in left..=right {
+            if let Some(color) = pattern(x as u32, y as u32) {
+                blend_pixel(img, x as u32, y as u32, &Source::new(color), opacity);
+            }
+        }
+    }
+}
+
+// 알파를 곱한 RGBA 이미지에서 (x, y) 를 쌍선형 샘플링 (이미지 밖은 투명, 결과는 알파를 곱하지 않은 색상)
+pub fn sample_bilinear(img: &RgbaImage, x: f32, y: f32) -> Option<Rgba<u8>> {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+    let neighbours = [
+        (0, 0, (1.0 - tx) * (1.0 - ty)),
+        (1, 0, tx * (1.0 - ty)),
+        (0, 1, (1.0 - tx) * ty),
+        (1, 1, tx * ty),
+    ];
+
+    let mut sum = [0.0f32; 4];
+    for (ox, oy, weight) in neighbours {
+        let (px, py) = (x0 as i64 + ox, y0 as i64 + oy);
+        if weight <= 0.0 || px < 0 || py < 0 || px >= img.width() as i64 || py >= img.height() as i64 {
+            continue;
+        }
+        let pixel = img.get_pixel(px as u32, py as u32).0;
+        for (total, channel) in sum.iter_mut().zip(pixel) {
+            *total += channel as f32 * weight;
+        }
+    }
+
+    let alpha = sum[3];
+    if alpha < 0.5 {
+        return None;
+    }
+    let demultiply = |c: f32| (c * 255.0 / alpha).round().min(255.0) as u8;
+    Some(Rgba([demultiply(sum[0]), demultiply(sum[1]), demultiply(sum[2]), alpha.round() as u8]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma, Rgb, Rgb32FImage, RgbImage};
+
+    const RED: Rgba<u8> = Rgba([255, 0, 0, 255]);
+    const HALF_RED: Rgba<u8> = Rgba([255, 0, 0, 128]);
+    const CLEAR_RED: Rgba<u8> = Rgba([255, 0, 0, 0]);
+
+    // 같은 1x1 픽셀을 형식별로 변환
+    fn formats(pixel: Rgba<u8>) -> Vec<(&'static str, DynamicImage)> {
+        let base = DynamicImage::ImageRgba8(RgbaImage::from_pixel(1, 1, pixel));
+        vec![
+            ("Rgb8", DynamicImage::ImageRgb8(base.to_rgb8())),
+            ("Rgba8", DynamicImage::ImageRgba8(base.to_rgba8())),
+            ("Luma8", DynamicImage::ImageLuma8(base.to_luma8())),
+            ("LumaA8", DynamicImage::ImageLumaA8(base.to_luma_alpha8())),
+            ("Rgb16", DynamicImage::ImageRgb16(base.to_rgb16())),
+            ("Rgba16", DynamicImage::ImageRgba16(base.to_rgba16())),
+            ("Rgb32F", DynamicImage::ImageRgb32F(base.to_rgb32f())),
+        ]
+    }
+
+    fn blended(mut img: DynamicImage, color: Rgba<u8>) -> [u8; 4] {
+        blend_pixel(&mut img, 0, 0, &Source::new(color), 1.0);
+        img.to_rgba8().get_pixel(0, 0).0
+    }
+
+    #[test]
+    fn blends_over_opaque_white_in_every_format() {
+        // 빨강의 밝기 0.2126 → 54, 반투명 (128/255) 이면 흰색과 섞여 154
+        for (name, img) in formats(Rgba([255, 255, 255, 255])) {
+            let gray = matches!(name, "Luma8" | "LumaA8");
+            let (opaque, half) = if gray {
+                ([54, 54, 54, 255], [154, 154, 154, 255])
+            } else {
+                ([255, 0, 0, 255], [255, 127, 127, 255])
+            };
+            assert_eq!(blended(img.clone(), RED), opaque, "{}", name);
+            assert_eq!(blended(img.clone(), HALF_RED), half, "{}", name);
+            assert_eq!(blended(img, CLEAR_RED), [255, 255, 255, 255], "{}", name);
+        }
+    }
+
+    #[test]
+    fn blends_over_transparent_pixel_keeping_source_alpha() {
+        for (name, img) in formats(Rgba([0, 0, 0, 0])) {
+            let expected = match name {
+                "Rgba8" | "Rgba16" => [255, 0, 0, 128],
+                "LumaA8" => [54, 54, 54, 128],
+                // 알파 채널이 없으면 검은색 위에 합성
+                "Luma8" => [27, 27, 27, 255],
+                _ => [128, 0, 0, 255],
+            };
+            assert_eq!(blended(img.clone(), HALF_RED), expected, "{}", name);
+            assert_eq!(blended(img.clone(), RED)[3], 255, "{}", name);
+            assert_eq!(blended(img.clone(), CLEAR_RED), img.to_rgba8().get_pixel(0, 0).0, "{}", name);
+        }
+    }
+
+    #[test]
+    fn keeps_source_precision() {
+        let mut img = DynamicImage::ImageRgb16(DynamicImage::ImageRgb8(RgbImage::from_pixel(1, 1, Rgb([255, 255, 255]))).to_rgb16());
+        blend_pixel(&mut img, 0, 0, &Source::new(HALF_RED), 1.0);
+        // 127/255 를 8비트로 반올림하지 않고 16비트로 저장
+        assert_eq!(img.as_rgb16().unwrap().get_pixel(0, 0).0, [65535, 127 * 257, 127 * 257]);
+
+        let mut img = DynamicImage::ImageRgb32F(Rgb32FImage::from_pixel(1, 1, Rgb([1.0, 1.0, 1.0])));
+        blend_pixel(&mut img, 0, 0, &Source::new(HALF_RED), 0.5);
+        let [r, g, _] = img.as_rgb32f().unwrap().get_pixel(0, 0).0;
+        assert!((r - 1.0).abs() < 1e-6 && (g - (1.0 - 64.0 / 255.0)).abs() < 1e-6, "{} {}", r, g);
+    }
+
+    fn painted(img: &DynamicImage) -> Vec<(u32, u32)> {
+        let luma = img.to_luma8();
+        luma.enumerate_pixels().filter(|(_, _, pixel)| pixel.0[0] < 255).map(|(x, y, _)| (x, y)).collect()
+    }
+
+    fn white(size: u32) -> DynamicImage {
+        DynamicImage::ImageLuma8(GrayImage::from_pixel(size, size, Luma([255])))
+    }
+
+    #[test]
+    fn fill_rect_clips_to_image() {
+        let mut img = white(4);
+        fill_rect(&mut img, Rect::at(-2, -2).of_size(4, 4), RED);
+        assert_eq!(painted(&img), vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        let mut img = white(4);
+        fill_rect(&mut img, Rect::at(3, 2).of_size(10, 1), RED);
+        assert_eq!(painted(&img), vec![(3, 2)]);
+
+        let mut img = white(4);
+        fill_rect(&mut img, Rect::at(4, 0).of_size(2, 2), RED);
+        fill_rect(&mut img, Rect::at(-3, -3).of_size(2, 2), RED);
+        assert!(painted(&img).is_empty());
+    }
+
+    #[test]
+    fn draw_image_clips_to_image() {
+        let overlay = RgbaImage::from_pixel(3, 3, RED);
+
+        let mut img = white(4);
+        draw_image(&mut img, &overlay, -1, -2, 1.0);
+        assert_eq!(painted(&img), vec![(0, 0), (1, 0)]);
+
+        let mut img = white(4);
+        draw_image(&mut img, &overlay, 2, 3, 1.0);
+        assert_eq!(painted(&img), vec![(2, 3), (3, 3)]);
+
+        let mut img = white(4);
+        draw_image(&mut img, &overlay, -3, 4, 1.0);
+        draw_image(&mut img, &overlay, 0, 0, 0.0);
+        assert!(painted(&img).is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+// 텍스트 회전 (임의 각도, 90 / 180 / 270 도는 빠른 경로)
+//
+// 텍스트를 배경 박스와 효과까지 포함해 투명한 라벨에 똑바로 그린 뒤,
+// 텍스트 영역 가운데를 중심으로 돌려서 합성합니다.
+// 90 / 180 / 270 도는 픽셀을 그대로 옮기므로 흐려지지 않고, 그 밖의 각도는 쌍선형 보간으로 돌립니다.
+// 배치(기준점)와 경계 체크에는 돌린 영역을 감싸는 사각형을 씁니다.
+
+use image::{imageops, DynamicImage, RgbaImage};
+use imageproc::rect::Rect;
+
+use crate::layout::TextBounds;
+use crate::render;
+use crate::watermark;
+
+// 90 도 단위로 볼 각도 오차 (도)
+const QUARTER_TURN_EPSILON: f32 = 1e-3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rotation {
+    // 0 이상 360 미만, 양수면 반시계 방향
+    degrees: f32,
+}
+
+impl Rotation {
+    pub const NONE: Rotation = Rotation { degrees: 0.0 };
+
+    pub fn from_degrees(degrees: f32) -> Result<Self, String> {
+        if !degrees.is_finite() {
+            return Err(format!("회전 각도가 유효하지 않습니다: {}", degrees));
+        }
+        Ok(Rotation { degrees: degrees.rem_euclid(360.0) })
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.quarter_turns() == Some(0)
+    }
+
+    // 90 도 단위 각도면 반시계 방향으로 몇 번 돌리는지 (0 ~ 3)
+    fn quarter_turns(&self) -> Option<u32> {
+        let turns = (self.degrees / 90.0).round();
+        ((self.degrees - turns * 90.0).abs() < QUARTER_TURN_EPSILON).then_some(turns as u32 % 4)
+    }
+
+    // 90 도 단위는 정확한 값 (돌린 영역이 반 픽셀씩 어긋나지 않도록)
+    fn sin_cos(&self) -> (f32, f32) {
+        match self.quarter_turns() {
+            Some(0) => (0.0, 1.0),
+            Some(1) => (1.0, 0.0),
+            Some(2) => (0.0, -1.0),
+            Some(3) => (-1.0, 0.0),
+            _ => self.degrees.to_radians().sin_cos(),
+        }
+    }
+
+    // pivot 을 중심으로 점을 돌림 (y 축이 아래쪽인 화면 좌표에서 반시계 방향)
+    fn rotate_point(&self, (x, y): (f32, f32), (pivot_x, pivot_y): (f32, f32)) -> (f32, f32) {
+        let (sin, cos) = self.sin_cos();
+        let (dx, dy) = (x - pivot_x, y - pivot_y);
+        (pivot_x + dx * cos + dy * sin, pivot_y - dx * sin + dy * cos)
+    }
+
+    // (x, y, width, height) 사각형을 돌렸을 때 감싸는 영역 (min_x, min_y, max_x, max_y)
+    fn rotated_extent(&self, x: f32, y: f32, width: f32, height: f32, pivot: (f32, f32)) -> (f32, f32, f32, f32) {
+        [(x, y), (x + width, y), (x, y + height), (x + width, y + height)]
+            .map(|corner| self.rotate_point(corner, pivot))
+            .iter()
+            .fold((f32::MAX, f32::MAX, f32::MIN, f32::MIN), |(min_x, min_y, max_x, max_y), &(x, y)| {
+                (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+            })
+    }
+
+    // bounds 를 pivot 중심으로 돌렸을 때 감싸는 사각형 (같은 그리기 원점 기준)
+    pub fn rotate_bounds(&self, bounds: &TextBounds, pivot: (f32, f32)) -> TextBounds {
+        if self.is_none() {
+            return *bounds;
+        }
+        let (min_x, min_y, max_x, max_y) = self.rotated_extent(
+            bounds.x as f32,
+            bounds.y as f32,
+            bounds.width as f32,
+            bounds.height as f32,
+            pivot,
+        );
+        let (left, top) = (min_x.floor(), min_y.floor());
+        TextBounds {
+            x: left as i32,
+            y: top as i32,
+            width: (max_x.ceil() - left).max(1.0) as u32,
+            height: (max_y.ceil() - top).max(1.0) as u32,
+        }
+    }
+
+    // 똑바로 그린 라벨(알파를 곱하지 않은 RGBA)을 돌려서 라벨의 pivot 이 이미지의 target 에 오도록 합성
+    pub fn draw(&self, img: &mut DynamicImage, label: &RgbaImage, pivot: (f32, f32), target: (f32, f32)) {
+        let (min_x, min_y, max_x, max_y) =
+            self.rotated_extent(0.0, 0.0, label.width() as f32, label.height() as f32, pivot);
+        // 라벨 좌표 → 이미지 좌표 이동량
+        let (shift_x, shift_y) = (target.0 - pivot.0, target.1 - pivot.1);
+
+        match self.quarter_turns() {
+            Some(turns) => {
+                // imageops 는 시계 방향으로 돌림
+                let rotated = match turns {
+                    0 => label.clone(),
+                    1 => imageops::rotate270(label),
+                    2 => imageops::rotate180(label),
+                    _ => imageops::rotate90(label),
+                };
+                let x = (min_x + shift_x).round() as i32;
+                let y = (min_y + shift_y).round() as i32;
+                render::draw_image(img, &rotated, x, y, 1.0);
+            }
+            None => {
+                let label = watermark::premultiply(label.clone());
+                let left = (min_x + shift_x).floor();
+                let top = (min_y + shift_y).floor();
+                let area = Rect::at(left as i32, top as i32).of_size(
+                    ((max_x + shift_x).ceil() - left).max(1.0) as u32,
+                    ((max_y + shift_y).ceil() - top).max(1.0) as u32,
+                );
+                let inverse = Rotation { degrees: 360.0 - self.degrees };
+                render::fill_pattern(img, area, 1.0, |x, y| {
+                    // 이미지 픽셀 가운데를 라벨 좌표로 되돌려 샘플링
+                    let point = (x as f32 + 0.5 - shift_x, y as f32 + 0.5 - shift_y);
+                    let (label_x, label_y) = inverse.rotate_point(point, pivot);
+                    render::sample_bilinear(&label, label_x - 0.5, label_y - 0.5)
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GenericImageView, Rgba};
+
+    fn rotation(degrees: f32) -> Rotation {
+        Rotation::from_degrees(degrees).unwrap()
+    }
+
+    // 가로로 긴 텍스트 영역 (가운데 (50, -10))
+    const BOUNDS: TextBounds = TextBounds { x: 0, y: -30, width: 100, height: 40 };
+
+    #[test]
+    fn from_degrees_wraps_and_rejects_non_finite() {
+        assert_eq!(rotation(-90.0), rotation(270.0));
+        assert_eq!(rotation(450.0), rotation(90.0));
+        assert!(rotation(360.0).is_none());
+        assert!(Rotation::from_degrees(f32::NAN).is_err());
+        assert!(Rotation::from_degrees(f32::INFINITY).is_err());
+    }
+
+    #[test]
+    fn quarter_turns_only_for_multiples_of_90() {
+        assert_eq!(rotation(0.0).quarter_turns(), Some(0));
+        assert_eq!(rotation(90.0).quarter_turns(), Some(1));
+        assert_eq!(rotation(180.0).quarter_turns(), Some(2));
+        assert_eq!(rotation(270.0).quarter_turns(), Some(3));
+        assert_eq!(rotation(-90.0).quarter_turns(), Some(3));
+        assert_eq!(rotation(89.9999).quarter_turns(), Some(1));
+        assert_eq!(rotation(359.9999).quarter_turns(), Some(0));
+        assert_eq!(rotation(45.0).quarter_turns(), None);
+        assert_eq!(rotation(91.0).quarter_turns(), None);
+    }
+
+    #[test]
+    fn rotate_point_is_counterclockwise_on_screen() {
+        // 오른쪽 점을 반시계로 90 도 돌리면 위쪽 (y 가 작아짐)
+        assert_eq!(rotation(90.0).rotate_point((10.0, 0.0), (0.0, 0.0)), (0.0, -10.0));
+        assert_eq!(rotation(180.0).rotate_point((10.0, 0.0), (0.0, 0.0)), (-10.0, 0.0));
+        assert_eq!(rotation(270.0).rotate_point((10.0, 0.0), (0.0, 0.0)), (0.0, 10.0));
+    }
+
+    #[test]
+    fn rotate_bounds_quarter_turns_around_center() {
+        let pivot = BOUNDS.center();
+        assert_eq!(pivot, (50.0, -10.0));
+        let upright = TextBounds { x: 30, y: -60, width: 40, height: 100 };
+        assert_eq!(rotation(90.0).rotate_bounds(&BOUNDS, pivot), upright);
+        assert_eq!(rotation(180.0).rotate_bounds(&BOUNDS, pivot), BOUNDS);
+        assert_eq!(rotation(270.0).rotate_bounds(&BOUNDS, pivot), upright);
+        assert_eq!(rotation(0.0).rotate_bounds(&BOUNDS, pivot), BOUNDS);
+    }
+
+    #[test]
+    fn rotate_bounds_any_angle_covers_rotated_corners() {
+        let pivot = BOUNDS.center();
+        let rotated = rotation(45.0).rotate_bounds(&BOUNDS, pivot);
+        // (100 + 40) / √2 ≈ 99 를 픽셀 단위로 감쌈
+        assert!((99..=101).contains(&rotated.width), "{:?}", rotated);
+        assert_eq!(rotated.width, rotated.height);
+        let (center_x, center_y) = rotated.center();
+        assert!((center_x - pivot.0).abs() <= 1.0 && (center_y - pivot.1).abs() <= 1.0, "{:?}", rotated);
+    }
+
+    #[test]
+    fn draw_quarter_turn_moves_pixels_exactly() {
+        // 왼쪽 빨강, 오른쪽 파랑 → 반시계로 90 도 돌리면 파랑이 위, 빨강이 아래
+        let red = Rgba([255, 0, 0, 255]);
+        let blue = Rgba([0, 0, 255, 255]);
+        let mut label = RgbaImage::new(2, 1);
+        label.put_pixel(0, 0, red);
+        label.put_pixel(1, 0, blue);
+
+        let mut img = DynamicImage::ImageRgba8(RgbaImage::from_pixel(10, 10, Rgba([0, 0, 0, 255])));
+        rotation(90.0).draw(&mut img, &label, (1.0, 0.5), (5.0, 5.0));
+        assert_eq!(img.get_pixel(5, 4), blue);
+        assert_eq!(img.get_pixel(5, 5), red);
+        let painted = img.pixels().filter(|(_, _, pixel)| *pixel != Rgba([0, 0, 0, 255])).count();
+        assert_eq!(painted, 2);
+    }
+}
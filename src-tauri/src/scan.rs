@@ -0,0 +1,228 @@
+// 입력 폴더 검색 (하위 폴더, 포함/제외 패턴, 숨김 파일, 심볼릭 링크)
+//
+// "client/date/*.jpg" 처럼 정리된 폴더를 통째로 처리할 수 있도록 하위 폴더까지 찾고,
+// 입력 폴더 기준 상대 경로(subfolder)를 함께 돌려줘서 출력 폴더에 같은 구조로 저장합니다.
+// 패턴은 입력 폴더 기준 상대 경로("2024/**/*.jpg")에 대소문자 구분 없이 적용합니다.
+// 형식은 파일 내용으로 판별해서 확장자와 다르면 결과에 알려줍니다.
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+use walkdir::{DirEntry, WalkDir};
+
+use crate::detect;
+
+// 이미지 확장자 (확장자가 없는 파일은 내용이 이미지일 때만 포함)
+const IMAGE_EXTENSIONS: [&str; 8] = ["jpg", "jpeg", "png", "bmp", "gif", "webp", "tif", "tiff"];
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkPolicy {
+    // 링크된 파일은 포함하고 링크된 폴더는 들어가지 않음 (기존 동작)
+    #[default]
+    Files,
+    // 링크된 폴더까지 따라감 (순환 링크는 건너뜀)
+    Follow,
+    // 심볼릭 링크는 모두 건너뜀
+    Skip,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScanOptions {
+    // 하위 폴더 검색 (false 면 기존처럼 입력 폴더 바로 아래만)
+    pub recursive: bool,
+    // 입력 폴더 아래 몇 단계까지 들어갈지 (없으면 제한 없음)
+    pub max_depth: Option<usize>,
+    // 비어 있으면 모든 이미지
+    pub include: Vec<String>,
+    // 폴더가 맞으면 그 아래 전체를 건너뜀
+    pub exclude: Vec<String>,
+    // '.' 으로 시작하는 파일/폴더 포함
+    pub include_hidden: bool,
+    pub symlinks: SymlinkPolicy,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScannedFile {
+    pub path: String,
+    // 입력 폴더 기준 상위 폴더 ("client/2024-01-01", 입력 폴더 바로 아래면 None)
+    pub subfolder: Option<String>,
+    // 파일 내용으로 판별한 형식 ("jpeg", "png" ...), 알 수 없으면 None
+    pub format: Option<String>,
+    // 확장자와 실제 형식이 다르거나 형식을 알 수 없을 때
+    pub warning: Option<String>,
+}
+
+// folder 아래의 이미지 파일 (상대 경로 순으로 정렬)
+pub fn scan_images(folder: &Path, options: &ScanOptions) -> Result<Vec<ScannedFile>, String> {
+    if !folder.is_dir() {
+        return Err(format!("폴더를 찾을 수 없습니다: {}", folder.display()));
+    }
+    let include = build_globs(&options.include)?;
+    let exclude = build_globs(&options.exclude)?;
+
+    // walkdir 깊이는 입력 폴더가 0, 바로 아래 파일이 1
+    let max_depth = match (options.recursive, options.max_depth) {
+        (false, _) => 1,
+        (true, Some(depth)) => depth.saturating_add(1),
+        (true, None) => usize::MAX,
+    };
+    let walker = WalkDir::new(folder)
+        .max_depth(max_depth)
+        .follow_links(options.symlinks == SymlinkPolicy::Follow)
+        .sort_by_file_name();
+
+    let mut files = Vec::new();
+    let entries = walker.into_iter().filter_entry(|entry| {
+        entry.depth() == 0 || (visible(entry, options) && !excluded(entry, folder, &exclude))
+    });
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            // 읽을 수 없는 하위 폴더, 순환 링크 등은 건너뛰고 계속 검색
+            Err(e) if e.depth() > 0 => {
+                eprintln!("검색 중 건너뜀: {}", e);
+                continue;
+            }
+            Err(e) => return Err(format!("폴더 읽기 실패: {}", e)),
+        };
+        let Some(has_extension) = image_candidate(&entry, options.symlinks) else {
+            continue;
+        };
+
+        let relative = relative_path(entry.path(), folder);
+        if !include.is_empty() && !include.is_match(&relative) {
+            continue;
+        }
+
+        let (format, warning) = match detect::sniff_file(entry.path()) {
+            Ok(format) if has_extension && !detect::extension_matches(entry.path(), format) => {
+                let name = detect::format_name(format);
+                (Some(format), Some(format!("확장자와 실제 형식({})이 다릅니다. {} 형식으로 처리합니다.", name, name)))
+            }
+            Ok(format) => (Some(format), None),
+            // 이미지 확장자인데 읽을 수 없는 파일은 목록에 남겨서 처리 결과에서 실패로 보여줌
+            Err(e) if has_extension => (None, Some(e)),
+            // 확장자 없는 파일은 이미지일 때만
+            Err(_) => continue,
+        };
+        files.push(ScannedFile {
+            path: entry.path().to_string_lossy().to_string(),
+            subfolder: relative.rsplit_once('/').map(|(dir, _)| dir.to_string()),
+            format: format.map(|format| format!("{:?}", format).to_lowercase()),
+            warning,
+        });
+    }
+
+    Ok(files)
+}
+
+// 출력 폴더 밖으로 나가지 않는 상대 경로인지 ("..", 절대 경로 거부)
+pub fn validate_subfolder(subfolder: &str) -> Result<(), String> {
+    let safe = Path::new(subfolder)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !safe {
+        return Err(format!("하위 폴더 경로가 유효하지 않습니다: {}", subfolder));
+    }
+    Ok(())
+}
+
+fn build_globs(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns.iter().map(|pattern| pattern.trim()).filter(|pattern| !pattern.is_empty()) {
+        let glob = GlobBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| format!("패턴이 유효하지 않습니다: {} ({})", pattern, e))?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| format!("패턴이 유효하지 않습니다: {}", e))
+}
+
+fn visible(entry: &DirEntry, options: &ScanOptions) -> bool {
+    if entry.path_is_symlink() && options.symlinks == SymlinkPolicy::Skip {
+        return false;
+    }
+    options.include_hidden || !entry.file_name().to_string_lossy().starts_with('.')
+}
+
+fn excluded(entry: &DirEntry, folder: &Path, exclude: &GlobSet) -> bool {
+    !exclude.is_empty() && exclude.is_match(relative_path(entry.path(), folder))
+}
+
+// 이미지 확장자 파일이면 Some(true), 확장자 없는 파일이면 Some(false) (내용을 확인해야 함)
+// 링크를 따라가지 않을 때도 링크된 파일은 포함 (SymlinkPolicy::Files)
+fn image_candidate(entry: &DirEntry, symlinks: SymlinkPolicy) -> Option<bool> {
+    let is_file = if entry.path_is_symlink() && symlinks == SymlinkPolicy::Files {
+        entry.path().is_file()
+    } else {
+        entry.file_type().is_file()
+    };
+    if !is_file {
+        return None;
+    }
+    match entry.path().extension().and_then(|e| e.to_str()) {
+        Some(extension) => IMAGE_EXTENSIONS.contains(&extension.to_lowercase().as_str()).then_some(true),
+        None => Some(false),
+    }
+}
+
+// 패턴과 출력 폴더 구조에 쓰는 '/' 구분 상대 경로
+fn relative_path(path: &Path, folder: &Path) -> String {
+    let relative = path.strip_prefix(folder).unwrap_or(path);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.to_string()).collect()
+    }
+
+    #[test]
+    fn validate_subfolder_accepts_relative_paths() {
+        for subfolder in ["client", "client/2024-01-01", "./client", "사진/여행", "a.b/c"] {
+            assert!(validate_subfolder(subfolder).is_ok(), "{:?}", subfolder);
+        }
+    }
+
+    #[test]
+    fn validate_subfolder_rejects_escaping_paths() {
+        for subfolder in ["..", "../out", "client/../../out", "client/..", "/etc", "/"] {
+            assert!(validate_subfolder(subfolder).is_err(), "{:?}", subfolder);
+        }
+    }
+
+    #[test]
+    fn build_globs_matches_relative_paths_case_insensitively() {
+        let globs = build_globs(&patterns(&["2024/**/*.jpg", " *.PNG "])).unwrap();
+        assert!(globs.is_match("2024/01/a.jpg"));
+        assert!(globs.is_match("2024/01/02/B.JPG"));
+        assert!(globs.is_match("cover.png"));
+        assert!(!globs.is_match("2023/01/a.jpg"));
+        assert!(!globs.is_match("2024/01/a.webp"));
+    }
+
+    #[test]
+    fn build_globs_skips_blank_patterns_and_rejects_invalid_ones() {
+        assert!(build_globs(&patterns(&["", "  "])).unwrap().is_empty());
+        assert!(build_globs(&patterns(&["[a-"])).is_err());
+        assert!(build_globs(&patterns(&["*.jpg", "{a,b"])).is_err());
+    }
+
+    #[test]
+    fn relative_path_uses_forward_slashes() {
+        let folder = Path::new("/photos");
+        assert_eq!(relative_path(&folder.join("client").join("a.jpg"), folder), "client/a.jpg");
+        assert_eq!(relative_path(&folder.join("a.jpg"), folder), "a.jpg");
+    }
+}
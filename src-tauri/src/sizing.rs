@@ -0,0 +1,162 @@
+// 폰트 크기 결정 방식 (이미지를 읽은 뒤 해상도에 맞춰 계산)
+//
+// 절대 px 외에 이미지 높이/짧은 변 대비 비율, 텍스트 너비를 이미지 너비의 N% 에 맞추는 방식을 지원합니다.
+// 상한은 고정값 대신 이미지 크기 기준으로 제한합니다.
+
+use serde::Deserialize;
+
+// 이보다 작은 글자는 읽을 수 없으므로 하한으로 사용 (이미지가 더 작으면 이미지 높이까지)
+pub const MIN_FONT_SIZE: f32 = 10.0;
+// fit-width 계산용 기준 크기
+const REFERENCE_FONT_SIZE: f32 = 100.0;
+// auto 방식의 기준 이미지 면적 (400x300)
+const AUTO_REFERENCE_AREA: f32 = 400.0 * 300.0;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum FontSizing {
+    // 기준 크기에 이미지 면적 비율의 제곱근을 곱함 (상한은 다른 방식과 같이 이미지 높이)
+    Auto { value: f32 },
+    // 절대 크기 (px)
+    Px { value: f32 },
+    // 이미지 높이의 N%
+    PercentHeight { value: f32 },
+    // 이미지 짧은 변의 N%
+    PercentShorterSide { value: f32 },
+    // 텍스트 너비가 이미지 너비의 N% 가 되도록
+    FitWidth { value: f32 },
+}
+
+impl FontSizing {
+    // sizing 이 있으면 font_size 대신 사용
+    pub fn from_args(font_size: f32, sizing: Option<FontSizing>) -> Result<Self, String> {
+        let sizing = sizing.unwrap_or(FontSizing::Px { value: font_size });
+        sizing.validate()?;
+        Ok(sizing)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let (value, max) = match *self {
+            FontSizing::Auto { value } | FontSizing::Px { value } => (value, f32::MAX),
+            FontSizing::PercentHeight { value } | FontSizing::PercentShorterSide { value } | FontSizing::FitWidth { value } => (value, 100.0),
+        };
+        if !(value.is_finite() && value > 0.0 && value <= max) {
+            return Err("폰트 크기가 유효하지 않습니다.".to_string());
+        }
+        Ok(())
+    }
+
+    // auto 는 반올림과 하한 때문에 줄인 이미지에서 다시 계산하면 크기가 조금씩 달라지므로
+    // 원본 해상도에서 px 로 고정 (미리보기에서 scaled 전에 호출)
+    pub fn pinned(&self, img_width: u32, img_height: u32) -> Self {
+        match *self {
+            FontSizing::Auto { .. } => FontSizing::Px { value: self.resolve(img_width, img_height, |_| 0.0) },
+            other => other,
+        }
+    }
+
+    // 미리보기용으로 이미지를 줄였을 때 (비율 방식은 줄인 이미지 기준으로 다시 계산되므로 그대로)
+    pub fn scaled(&self, factor: f32) -> Self {
+        match *self {
+            FontSizing::Px { value } => FontSizing::Px { value: value * factor },
+            relative => relative,
+        }
+    }
+
+    // 이미지 크기에 맞춰 실제 폰트 크기(px) 계산
+    // text_width 는 주어진 폰트 크기에서 텍스트 블록의 너비를 돌려줌 (fit-width 용)
+    pub fn resolve(&self, img_width: u32, img_height: u32, text_width: impl Fn(f32) -> f32) -> f32 {
+        let size = match *self {
+            FontSizing::Auto { value } => {
+                let area = img_width as f32 * img_height as f32;
+                (value * (area / AUTO_REFERENCE_AREA).sqrt()).round()
+            }
+            FontSizing::Px { value } => value,
+            FontSizing::PercentHeight { value } => img_height as f32 * value / 100.0,
+            FontSizing::PercentShorterSide { value } => img_width.min(img_height) as f32 * value / 100.0,
+            FontSizing::FitWidth { value } => {
+                // 글리프 크기는 폰트 크기에 비례하므로 기준 크기에서 한 번 측정해서 환산
+                let reference_width = text_width(REFERENCE_FONT_SIZE);
+                if reference_width > 0.0 {
+                    REFERENCE_FONT_SIZE * (img_width as f32 * value / 100.0) / reference_width
+                } else {
+                    MIN_FONT_SIZE
+                }
+            }
+        };
+        clamp_font_size(size, img_height)
+    }
+}
+
+// 하한은 MIN_FONT_SIZE, 상한은 이미지 높이 (이미지보다 큰 글자는 의미 없음)
+pub fn clamp_font_size(font_size: f32, img_height: u32) -> f32 {
+    let max = (img_height as f32).max(1.0);
+    font_size.clamp(MIN_FONT_SIZE.min(max), max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 글자 너비가 폰트 크기의 2배인 텍스트
+    fn double_width(font_size: f32) -> f32 {
+        font_size * 2.0
+    }
+
+    #[test]
+    fn px_is_used_as_is_within_image_height() {
+        assert_eq!(FontSizing::Px { value: 48.0 }.resolve(800, 600, double_width), 48.0);
+        assert_eq!(FontSizing::Px { value: 900.0 }.resolve(800, 600, double_width), 600.0);
+        assert_eq!(FontSizing::Px { value: 2.0 }.resolve(800, 600, double_width), MIN_FONT_SIZE);
+    }
+
+    #[test]
+    fn percent_modes_follow_image_size() {
+        assert_eq!(FontSizing::PercentHeight { value: 10.0 }.resolve(4000, 3000, double_width), 300.0);
+        assert_eq!(FontSizing::PercentShorterSide { value: 5.0 }.resolve(3000, 4000, double_width), 150.0);
+        assert_eq!(FontSizing::PercentShorterSide { value: 5.0 }.resolve(4000, 3000, double_width), 150.0);
+    }
+
+    #[test]
+    fn fit_width_scales_text_to_image_width() {
+        // 이미지 너비 1000 의 20% = 200px 너비 → 폰트 크기 100
+        assert_eq!(FontSizing::FitWidth { value: 20.0 }.resolve(1000, 800, double_width), 100.0);
+        // 측정할 글자가 없으면 하한
+        assert_eq!(FontSizing::FitWidth { value: 20.0 }.resolve(1000, 800, |_| 0.0), MIN_FONT_SIZE);
+    }
+
+    #[test]
+    fn auto_scales_with_area_without_fixed_cap() {
+        assert_eq!(FontSizing::Auto { value: 20.0 }.resolve(400, 300, double_width), 20.0);
+        // 50MP 급 이미지에서도 200px 에서 멈추지 않음
+        assert_eq!(FontSizing::Auto { value: 20.0 }.resolve(8000, 6000, double_width), 400.0);
+        assert_eq!(FontSizing::Auto { value: 20.0 }.pinned(8000, 6000), FontSizing::Px { value: 400.0 });
+    }
+
+    #[test]
+    fn scaled_only_changes_px() {
+        assert_eq!(FontSizing::Px { value: 40.0 }.scaled(0.25), FontSizing::Px { value: 10.0 });
+        let relative = FontSizing::PercentHeight { value: 10.0 };
+        assert_eq!(relative.scaled(0.25), relative);
+    }
+
+    #[test]
+    fn from_args_validates_range() {
+        assert_eq!(FontSizing::from_args(32.0, None), Ok(FontSizing::Px { value: 32.0 }));
+        assert!(FontSizing::from_args(32.0, Some(FontSizing::PercentHeight { value: 150.0 })).is_err());
+        assert!(FontSizing::from_args(0.0, None).is_err());
+        assert!(FontSizing::from_args(f32::NAN, None).is_err());
+    }
+
+    #[test]
+    fn deserializes_tagged_mode() {
+        let sizing: FontSizing = serde_json::from_str(r#"{"mode":"percentShorterSide","value":5}"#).unwrap();
+        assert_eq!(sizing, FontSizing::PercentShorterSide { value: 5.0 });
+    }
+
+    #[test]
+    fn clamp_allows_images_smaller_than_minimum() {
+        assert_eq!(clamp_font_size(40.0, 6), 6.0);
+        assert_eq!(clamp_font_size(2.0, 6), 6.0);
+    }
+}
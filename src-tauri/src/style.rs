@@ -0,0 +1,427 @@
+// 텍스트 스타일 (글자색, 배경색/투명도, 패딩, 회전)
+//
+// 프론트엔드에서 받은 값은 TextStyleOptions 로 받고, resolve 에서 검증한 뒤
+// 렌더러가 쓰는 TextStyle 로 바꿉니다. 지정하지 않은 항목은 렌더러별 기본값을 사용합니다.
+
+use image::Rgba;
+use serde::Deserialize;
+
+use crate::layout::{LayoutOptions, TextAlign};
+use crate::rotation::Rotation;
+
+// 패딩 최대값 (px)
+const MAX_PADDING: u32 = 200;
+// 외곽선 두께, 그림자 오프셋/흐림, 글로우 반경 최대값 (px)
+const MAX_EFFECT_SIZE: f32 = 50.0;
+// 줄 간격 배율 범위
+const LINE_HEIGHT_RANGE: std::ops::RangeInclusive<f32> = 0.5..=5.0;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TextStyleOptions {
+    // "#RGB", "#RRGGBB", "#RRGGBBAA"
+    pub text_color: Option<String>,
+    // 색상 문자열 또는 "none" / "transparent" (배경 없음)
+    pub background_color: Option<String>,
+    // 0.0 ~ 1.0, 배경색의 알파값에 곱해짐
+    pub background_opacity: Option<f32>,
+    pub padding: Option<u32>,
+    pub stroke: Option<StrokeOptions>,
+    pub shadow: Option<ShadowOptions>,
+    pub glow: Option<GlowOptions>,
+    // 여러 줄 텍스트: 정렬, 줄 간격 배율, 자동 줄바꿈 너비(px)
+    pub align: Option<TextAlign>,
+    pub line_height: Option<f32>,
+    pub max_width: Option<f32>,
+    // 자동 줄바꿈 너비를 이미지 너비의 N% 로 지정 (max_width 보다 우선, 이미지마다 다시 계산)
+    pub max_width_percent: Option<f32>,
+    // 회전 각도 (도, 양수면 반시계 방향, 배경 박스도 함께 돌아감)
+    pub rotation: Option<f32>,
+}
+
+// 외곽선 (기본: 검정 2px)
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StrokeOptions {
+    pub width: Option<f32>,
+    pub color: Option<String>,
+}
+
+// 그림자 (기본: 오른쪽 아래 2px, 흐림 3px, 반투명 검정)
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShadowOptions {
+    pub offset_x: Option<f32>,
+    pub offset_y: Option<f32>,
+    pub blur: Option<f32>,
+    pub color: Option<String>,
+}
+
+// 글로우 (기본: 반경 6px 흰색)
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GlowOptions {
+    pub radius: Option<f32>,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub text_color: Rgba<u8>,
+    // None 이면 배경 박스를 그리지 않음
+    pub background: Option<Rgba<u8>>,
+    pub padding: u32,
+    pub stroke: Option<Stroke>,
+    pub shadow: Option<Shadow>,
+    pub glow: Option<Glow>,
+    pub layout: LayoutOptions,
+    pub rotation: Rotation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stroke {
+    pub width: f32,
+    pub color: Rgba<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    pub offset_x: i32,
+    pub offset_y: i32,
+    pub blur: f32,
+    pub color: Rgba<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glow {
+    pub radius: f32,
+    pub color: Rgba<u8>,
+}
+
+impl TextStyle {
+    // add_text_to_image_simple 기본값: 빨간 글자 + 약간 불투명한 흰 배경
+    pub const SIMPLE: TextStyle = TextStyle {
+        text_color: Rgba([255, 0, 0, 255]),
+        background: Some(Rgba([255, 255, 255, 245])),
+        padding: 2,
+        stroke: None,
+        shadow: None,
+        glow: None,
+        layout: LayoutOptions::DEFAULT,
+        rotation: Rotation::NONE,
+    };
+
+    // add_text_to_image (기존 미리보기) 기본값
+    pub const LEGACY: TextStyle = TextStyle {
+        text_color: Rgba([255, 0, 0, 255]),
+        background: Some(Rgba([255, 255, 255, 220])),
+        padding: 8,
+        stroke: None,
+        shadow: None,
+        glow: None,
+        layout: LayoutOptions::DEFAULT,
+        rotation: Rotation::NONE,
+    };
+
+    pub fn has_effects(&self) -> bool {
+        self.stroke.is_some() || self.shadow.is_some() || self.glow.is_some()
+    }
+
+    // 외곽선 두께 (없으면 0)
+    pub fn stroke_width(&self) -> f32 {
+        self.stroke.map_or(0.0, |stroke| stroke.width)
+    }
+
+    // 글리프 마스크 주변에 필요한 여백 (외곽선 + 흐림/글로우가 퍼지는 범위, 오프셋 제외)
+    pub fn effect_spread(&self) -> i32 {
+        let glow = self.glow.map_or(0.0, |glow| glow.radius * 2.0);
+        let blur = self.shadow.map_or(0.0, |shadow| shadow.blur * 2.0);
+        (self.stroke_width() + glow.max(blur)).ceil() as i32 + 1
+    }
+
+    // 텍스트 영역 밖으로 효과가 그려지는 최대 거리 (경계 체크용)
+    pub fn effect_extent(&self) -> i32 {
+        if !self.has_effects() {
+            return 0;
+        }
+        let offset = self.shadow.map_or(0, |shadow| shadow.offset_x.abs().max(shadow.offset_y.abs()));
+        self.effect_spread() + offset
+    }
+}
+
+impl TextStyleOptions {
+    // 이미지 너비를 알 때 사용 (max_width_percent 를 px 로 바꿈)
+    pub fn resolve_for_image(&self, defaults: TextStyle, img_width: u32) -> Result<TextStyle, String> {
+        let mut style = self.resolve(defaults)?;
+        if let Some(percent) = self.max_width_percent {
+            style.layout.max_width = Some((img_width as f32 * percent / 100.0).floor().max(1.0));
+        }
+        Ok(style)
+    }
+
+    // 값 검증 후 지정하지 않은 항목은 defaults 로 채움
+    pub fn resolve(&self, defaults: TextStyle) -> Result<TextStyle, String> {
+        let text_color = match &self.text_color {
+            Some(color) => parse_color(color).map_err(|e| format!("글자 색상이 유효하지 않습니다: {}", e))?,
+            None => defaults.text_color,
+        };
+
+        let background = match self.background_color.as_deref().map(str::trim) {
+            Some(color) if is_no_background(color) => None,
+            Some(color) => Some(parse_color(color).map_err(|e| format!("배경 색상이 유효하지 않습니다: {}", e))?),
+            None => defaults.background,
+        };
+
+        let background = match self.background_opacity {
+            Some(opacity) if !(0.0..=1.0).contains(&opacity) => {
+                return Err(format!("배경 투명도는 0.0 ~ 1.0 사이여야 합니다: {}", opacity));
+            }
+            Some(opacity) => background.map(|Rgba([r, g, b, a])| Rgba([r, g, b, (a as f32 * opacity).round() as u8])),
+            None => background,
+        };
+
+        let padding = self.padding.unwrap_or(defaults.padding);
+        if padding > MAX_PADDING {
+            return Err(format!("패딩은 {}px 이하여야 합니다: {}", MAX_PADDING, padding));
+        }
+
+        let stroke = match &self.stroke {
+            Some(stroke) => {
+                let width = effect_size(stroke.width.unwrap_or(2.0), "외곽선 두께")?;
+                let color = optional_color(&stroke.color, Rgba([0, 0, 0, 255]), "외곽선 색상")?;
+                Some(Stroke { width, color }).filter(|stroke| stroke.width > 0.0)
+            }
+            None => defaults.stroke,
+        };
+
+        let shadow = match &self.shadow {
+            Some(shadow) => Some(Shadow {
+                offset_x: effect_offset(shadow.offset_x.unwrap_or(2.0), "그림자 가로 오프셋")?,
+                offset_y: effect_offset(shadow.offset_y.unwrap_or(2.0), "그림자 세로 오프셋")?,
+                blur: effect_size(shadow.blur.unwrap_or(3.0), "그림자 흐림")?,
+                color: optional_color(&shadow.color, Rgba([0, 0, 0, 160]), "그림자 색상")?,
+            }),
+            None => defaults.shadow,
+        };
+
+        let glow = match &self.glow {
+            Some(glow) => {
+                let radius = effect_size(glow.radius.unwrap_or(6.0), "글로우 반경")?;
+                let color = optional_color(&glow.color, Rgba([255, 255, 255, 255]), "글로우 색상")?;
+                Some(Glow { radius, color }).filter(|glow| glow.radius > 0.0)
+            }
+            None => defaults.glow,
+        };
+
+        let line_height = self.line_height.unwrap_or(defaults.layout.line_height);
+        if !LINE_HEIGHT_RANGE.contains(&line_height) {
+            return Err(format!(
+                "줄 간격은 {} ~ {} 사이여야 합니다: {}",
+                LINE_HEIGHT_RANGE.start(),
+                LINE_HEIGHT_RANGE.end(),
+                line_height
+            ));
+        }
+
+        if let Some(percent) = self.max_width_percent {
+            if !(percent.is_finite() && percent > 0.0 && percent <= 100.0) {
+                return Err(format!("줄바꿈 너비 비율이 유효하지 않습니다: {}", percent));
+            }
+        }
+
+        let max_width = match self.max_width {
+            Some(max_width) if !(max_width.is_finite() && max_width > 0.0) => {
+                return Err(format!("줄바꿈 너비가 유효하지 않습니다: {}", max_width));
+            }
+            Some(max_width) => Some(max_width),
+            None => defaults.layout.max_width,
+        };
+
+        let rotation = match self.rotation {
+            Some(degrees) => Rotation::from_degrees(degrees)?,
+            None => defaults.rotation,
+        };
+
+        let layout = LayoutOptions {
+            max_width,
+            align: self.align.unwrap_or(defaults.layout.align),
+            line_height,
+        };
+
+        Ok(TextStyle {
+            text_color,
+            // 완전히 투명한 배경은 그리지 않는 것과 같음
+            background: background.filter(|color| color.0[3] > 0),
+            padding,
+            // 투명한 효과는 그리지 않음
+            stroke: stroke.filter(|stroke| stroke.color.0[3] > 0),
+            shadow: shadow.filter(|shadow| shadow.color.0[3] > 0),
+            glow: glow.filter(|glow| glow.color.0[3] > 0),
+            layout,
+            rotation,
+        })
+    }
+}
+
+fn optional_color(color: &Option<String>, default: Rgba<u8>, label: &str) -> Result<Rgba<u8>, String> {
+    match color {
+        Some(color) => parse_color(color).map_err(|e| format!("{}이 유효하지 않습니다: {}", label, e)),
+        None => Ok(default),
+    }
+}
+
+fn effect_size(value: f32, label: &str) -> Result<f32, String> {
+    if !(0.0..=MAX_EFFECT_SIZE).contains(&value) {
+        return Err(format!("{} 값은 0 ~ {}px 사이여야 합니다: {}", label, MAX_EFFECT_SIZE, value));
+    }
+    Ok(value)
+}
+
+fn effect_offset(value: f32, label: &str) -> Result<i32, String> {
+    if !(-MAX_EFFECT_SIZE..=MAX_EFFECT_SIZE).contains(&value) {
+        return Err(format!("{} 값은 -{} ~ {}px 사이여야 합니다: {}", label, MAX_EFFECT_SIZE, MAX_EFFECT_SIZE, value));
+    }
+    Ok(value.round() as i32)
+}
+
+fn is_no_background(color: &str) -> bool {
+    color.is_empty() || color.eq_ignore_ascii_case("none") || color.eq_ignore_ascii_case("transparent")
+}
+
+// "#RGB", "#RRGGBB", "#RRGGBBAA" 형식의 색상 파싱
+pub fn parse_color(color: &str) -> Result<Rgba<u8>, String> {
+    let hex = color.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(color.to_string());
+    }
+
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| color.to_string());
+
+    match hex.len() {
+        3 => {
+            let short = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).map(|v| v * 17).map_err(|_| color.to_string());
+            Ok(Rgba([short(0)?, short(1)?, short(2)?, 255]))
+        }
+        6 => Ok(Rgba([channel(0)?, channel(2)?, channel(4)?, 255])),
+        8 => Ok(Rgba([channel(0)?, channel(2)?, channel(4)?, channel(6)?])),
+        _ => Err(color.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(options: TextStyleOptions) -> Result<TextStyle, String> {
+        options.resolve(TextStyle::SIMPLE)
+    }
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(parse_color("#f80"), Ok(Rgba([255, 136, 0, 255])));
+        assert_eq!(parse_color("#FF8800"), Ok(Rgba([255, 136, 0, 255])));
+        assert_eq!(parse_color("ff880080"), Ok(Rgba([255, 136, 0, 128])));
+        assert_eq!(parse_color(" #00000000 "), Ok(Rgba([0, 0, 0, 0])));
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        for color in ["", "#", "#ff", "#ff88", "#ff8800f", "#gg0000", "red", "#ff 880", "#ＦＦ0000"] {
+            assert_eq!(parse_color(color), Err(color.to_string()), "{:?}", color);
+        }
+    }
+
+    #[test]
+    fn unset_options_use_defaults() {
+        assert_eq!(resolve(TextStyleOptions::default()), Ok(TextStyle::SIMPLE));
+        assert_eq!(TextStyleOptions::default().resolve(TextStyle::LEGACY), Ok(TextStyle::LEGACY));
+    }
+
+    #[test]
+    fn background_opacity_scales_alpha() {
+        let style = resolve(TextStyleOptions {
+            background_color: Some("#000000".to_string()),
+            background_opacity: Some(0.5),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(style.background, Some(Rgba([0, 0, 0, 128])));
+
+        // 투명도 0 이나 "none" 은 배경 없음
+        for (color, opacity) in [("#000000", 0.0), ("none", 1.0), ("transparent", 1.0), ("", 1.0)] {
+            let options = TextStyleOptions {
+                background_color: Some(color.to_string()),
+                background_opacity: Some(opacity),
+                ..Default::default()
+            };
+            assert_eq!(resolve(options).unwrap().background, None, "{:?}", color);
+        }
+    }
+
+    #[test]
+    fn rejects_values_out_of_range() {
+        let cases = [
+            TextStyleOptions { text_color: Some("#12".to_string()), ..Default::default() },
+            TextStyleOptions { background_color: Some("blue".to_string()), ..Default::default() },
+            TextStyleOptions { background_opacity: Some(1.5), ..Default::default() },
+            TextStyleOptions { background_opacity: Some(-0.1), ..Default::default() },
+            TextStyleOptions { background_opacity: Some(f32::NAN), ..Default::default() },
+            TextStyleOptions { padding: Some(MAX_PADDING + 1), ..Default::default() },
+            TextStyleOptions { line_height: Some(0.4), ..Default::default() },
+            TextStyleOptions { line_height: Some(5.1), ..Default::default() },
+            TextStyleOptions { max_width: Some(0.0), ..Default::default() },
+            TextStyleOptions { max_width: Some(f32::INFINITY), ..Default::default() },
+            TextStyleOptions { max_width_percent: Some(0.0), ..Default::default() },
+            TextStyleOptions { max_width_percent: Some(101.0), ..Default::default() },
+            TextStyleOptions {
+                stroke: Some(StrokeOptions { width: Some(MAX_EFFECT_SIZE + 1.0), color: None }),
+                ..Default::default()
+            },
+            TextStyleOptions {
+                shadow: Some(ShadowOptions { offset_x: Some(-51.0), ..Default::default() }),
+                ..Default::default()
+            },
+            TextStyleOptions {
+                shadow: Some(ShadowOptions { blur: Some(-1.0), ..Default::default() }),
+                ..Default::default()
+            },
+            TextStyleOptions {
+                glow: Some(GlowOptions { radius: Some(f32::NAN), color: None }),
+                ..Default::default()
+            },
+            TextStyleOptions {
+                glow: Some(GlowOptions { radius: None, color: Some("#xyz".to_string()) }),
+                ..Default::default()
+            },
+        ];
+        for options in cases {
+            assert!(resolve(options.clone()).is_err(), "{:?}", options);
+        }
+    }
+
+    #[test]
+    fn accepts_range_limits() {
+        let style = resolve(TextStyleOptions {
+            background_opacity: Some(1.0),
+            padding: Some(MAX_PADDING),
+            line_height: Some(0.5),
+            max_width_percent: Some(100.0),
+            shadow: Some(ShadowOptions { offset_x: Some(-MAX_EFFECT_SIZE), offset_y: Some(MAX_EFFECT_SIZE), ..Default::default() }),
+            stroke: Some(StrokeOptions { width: Some(0.0), color: None }),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(style.padding, MAX_PADDING);
+        assert_eq!(style.shadow.map(|shadow| (shadow.offset_x, shadow.offset_y)), Some((-50, 50)));
+        // 두께 0 인 외곽선은 그리지 않음
+        assert_eq!(style.stroke, None);
+    }
+
+    #[test]
+    fn max_width_percent_uses_image_width() {
+        let options = TextStyleOptions { max_width: Some(100.0), max_width_percent: Some(25.0), ..Default::default() };
+        assert_eq!(options.resolve_for_image(TextStyle::SIMPLE, 1000).unwrap().layout.max_width, Some(250.0));
+        assert_eq!(options.resolve_for_image(TextStyle::SIMPLE, 2).unwrap().layout.max_width, Some(1.0));
+        assert_eq!(options.resolve(TextStyle::SIMPLE).unwrap().layout.max_width, Some(100.0));
+    }
+}
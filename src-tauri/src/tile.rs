@@ -0,0 +1,256 @@
+// 반복 워터마크 (시안용 사선 패턴)
+//
+// 텍스트나 로고를 이미지 전체에 일정한 간격으로 기울여 깔아서
+// 모서리 워터마크처럼 잘라내고 쓸 수 없게 합니다.
+// 한 칸(글자 또는 로고)만 그려 두고 이미지의 각 픽셀을 패턴 좌표로 되돌려 돌린 뒤
+// 그 칸의 픽셀을 샘플링하므로, 각도와 관계없이 가장자리까지 빈틈 없이 채워집니다.
+// 크기와 간격은 이미지 짧은 변에 대한 비율이라 해상도가 달라도 같은 모양입니다.
+
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+use imageproc::rect::Rect;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::fonts::FontChain;
+use crate::layout::{self, LayoutOptions};
+use crate::render;
+use crate::style;
+use crate::unicode;
+use crate::watermark::{self, Logo, LogoCache};
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TileOptions {
+    // 반복할 텍스트 (없으면 이미지마다 넣는 텍스트 그대로)
+    pub text: Option<String>,
+    // 로고 파일 경로 (지정하면 텍스트 대신 로고를 반복)
+    pub logo: Option<String>,
+    // 텍스트 색상 ("#RRGGBB")
+    pub color: String,
+    // 한 칸 크기: 텍스트는 글자 높이, 로고는 로고 너비 (이미지 짧은 변에 대한 %)
+    pub size_percent: f32,
+    // 칸 사이 간격 (이미지 짧은 변에 대한 %)
+    pub spacing_percent: f32,
+    // 기울기 (도, 양수면 오른쪽 위로 올라감)
+    pub angle: f32,
+    // 0.0 (투명) ~ 1.0 (불투명)
+    pub opacity: f32,
+    // 줄마다 가로로 밀어낼 칸 비율 (0 이면 격자, 0.5 면 벽돌 모양)
+    pub stagger: f32,
+}
+
+impl Default for TileOptions {
+    fn default() -> Self {
+        TileOptions {
+            text: None,
+            logo: None,
+            color: "#ffffff".to_string(),
+            size_percent: 5.0,
+            spacing_percent: 8.0,
+            angle: 30.0,
+            opacity: 0.25,
+            stagger: 0.5,
+        }
+    }
+}
+
+impl TileOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.size_percent.is_finite() && self.size_percent > 0.0 && self.size_percent <= 100.0) {
+            return Err(format!("반복 워터마크 크기는 0 ~ 100% 사이여야 합니다: {}", self.size_percent));
+        }
+        if !(0.0..=100.0).contains(&self.spacing_percent) {
+            return Err(format!("반복 워터마크 간격은 0 ~ 100% 사이여야 합니다: {}", self.spacing_percent));
+        }
+        if !self.angle.is_finite() {
+            return Err(format!("반복 워터마크 각도가 유효하지 않습니다: {}", self.angle));
+        }
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err(format!("반복 워터마크 불투명도는 0 ~ 1 사이여야 합니다: {}", self.opacity));
+        }
+        if !(0.0..=1.0).contains(&self.stagger) {
+            return Err(format!("반복 워터마크 엇갈림은 0 ~ 1 사이여야 합니다: {}", self.stagger));
+        }
+        Ok(())
+    }
+}
+
+// 읽어 둔 로고 (또는 반복할 텍스트) + 패턴 설정
+pub struct Tile {
+    text: Option<String>,
+    logo: Option<Arc<Logo>>,
+    color: Rgba<u8>,
+    size_percent: f32,
+    spacing_percent: f32,
+    angle: f32,
+    opacity: f32,
+    stagger: f32,
+}
+
+impl Tile {
+    pub fn load(options: &TileOptions, logos: &LogoCache) -> Result<Self, String> {
+        options.validate()?;
+        let color = style::parse_color(&options.color)
+            .map_err(|e| format!("반복 워터마크 색상이 유효하지 않습니다: {}", e))?;
+        let logo = match options.logo.as_deref().filter(|path| !path.is_empty()) {
+            Some(path) => Some(logos.load(path)?),
+            None => None,
+        };
+
+        Ok(Tile {
+            text: options.text.clone().filter(|text| !text.trim().is_empty()),
+            logo,
+            color,
+            size_percent: options.size_percent,
+            spacing_percent: options.spacing_percent,
+            angle: options.angle,
+            opacity: options.opacity,
+            stagger: options.stagger,
+        })
+    }
+
+    // 이미지 전체에 패턴 합성 (text: 반복할 텍스트를 지정하지 않았을 때 쓰는 이미지 텍스트)
+    pub fn apply(&self, img: &mut DynamicImage, fonts: &FontChain, text: &str) {
+        if self.opacity <= 0.0 {
+            return;
+        }
+        let (img_width, img_height) = img.dimensions();
+        let short_side = img_width.min(img_height) as f32;
+        let Some(cell) = self.cell(fonts, text, short_side) else {
+            return;
+        };
+
+        let (cell_width, cell_height) = (cell.width() as f32, cell.height() as f32);
+        let gap = short_side * self.spacing_percent / 100.0;
+        let (step_x, step_y) = (cell_width + gap, cell_height + gap);
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let (center_x, center_y) = (img_width as f32 / 2.0, img_height as f32 / 2.0);
+
+        let area = Rect::at(0, 0).of_size(img_width, img_height);
+        render::fill_pattern(img, area, self.opacity, |x, y| {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            // 패턴 좌표 (이미지 가운데에 한 칸이 오도록 반 칸 이동)
+            let u = dx * cos - dy * sin + cell_width / 2.0;
+            let v = dx * sin + dy * cos + cell_height / 2.0;
+            let row = (v / step_y).floor();
+            let cell_x = (u - row * self.stagger * step_x).rem_euclid(step_x);
+            let cell_y = v.rem_euclid(step_y);
+            render::sample_bilinear(&cell, cell_x - 0.5, cell_y - 0.5)
+        });
+    }
+
+    // 한 칸 (알파를 곱한 RGBA)
+    fn cell(&self, fonts: &FontChain, text: &str, short_side: f32) -> Option<RgbaImage> {
+        let size = (short_side * self.size_percent / 100.0).round().max(1.0);
+        if let Some(logo) = &self.logo {
+            return logo.render(size as u32);
+        }
+
+        let text = unicode::to_nfc(self.text.as_deref().unwrap_or(text));
+        if fonts.is_empty() || text.trim().is_empty() {
+            return None;
+        }
+        let text_layout = layout::layout_text(fonts, &text, size, &LayoutOptions::default());
+        let bounds = text_layout.metrics.bounds;
+        if bounds.width == 0 || bounds.height == 0 {
+            return None;
+        }
+
+        let mut cell = DynamicImage::ImageRgba8(RgbaImage::new(bounds.width, bounds.height));
+        render::draw_glyphs(&mut cell, self.color, -bounds.x, -bounds.y, &text_layout.glyphs);
+        Some(watermark::premultiply(cell.into_rgba8()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    // 200x200 이미지 → 짧은 변의 10% = 20px 칸, 간격 20px (칸마다 40px)
+    fn logo_tile(angle: f32, stagger: f32) -> Tile {
+        let logo = RgbaImage::from_pixel(10, 10, Rgba([255, 0, 0, 255]));
+        Tile {
+            text: None,
+            logo: Some(Arc::new(Logo::Raster(watermark::premultiply(logo)))),
+            color: Rgba([255, 255, 255, 255]),
+            size_percent: 10.0,
+            spacing_percent: 10.0,
+            angle,
+            opacity: 1.0,
+            stagger,
+        }
+    }
+
+    fn tiled(tile: &Tile) -> RgbImage {
+        let mut img = DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 200, Rgb([255, 255, 255])));
+        tile.apply(&mut img, &FontChain::default(), "");
+        img.into_rgb8()
+    }
+
+    fn is_painted(img: &RgbImage, x: u32, y: u32) -> bool {
+        img.get_pixel(x, y).0 != [255, 255, 255]
+    }
+
+    #[test]
+    fn grid_cells_are_centered_and_evenly_spaced() {
+        let img = tiled(&logo_tile(0.0, 0.0));
+
+        // 가운데 칸은 (90, 90) ~ (110, 110), 40px 마다 반복 → 5 x 5 칸
+        let starts = [10, 50, 90, 130, 170];
+        let painted = img.enumerate_pixels().filter(|(_, _, pixel)| pixel.0 != [255, 255, 255]).count();
+        assert_eq!(painted, starts.len() * starts.len() * 20 * 20);
+        for &y in &starts {
+            for &x in &starts {
+                assert!(is_painted(&img, x, y) && is_painted(&img, x + 19, y + 19), "({}, {})", x, y);
+                assert!(!is_painted(&img, x + 20, y) && !is_painted(&img, x, y + 20), "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn stagger_shifts_every_other_row() {
+        let grid = tiled(&logo_tile(0.0, 0.0));
+        let brick = tiled(&logo_tile(0.0, 0.5));
+
+        // 가운데 줄은 그대로, 다음 줄은 반 칸 (20px) 오른쪽으로
+        assert!(is_painted(&brick, 100, 100));
+        assert!(is_painted(&grid, 100, 140) && !is_painted(&brick, 100, 140));
+        assert!(!is_painted(&grid, 120, 140) && is_painted(&brick, 120, 140));
+    }
+
+    #[test]
+    fn rotated_pattern_still_covers_center() {
+        let img = tiled(&logo_tile(45.0, 0.0));
+        assert!(is_painted(&img, 100, 100));
+        // 45도 돌린 20px 칸은 마름모: 가로로는 14px 까지 칠해지고 대각선 (10, 10) 은 칸 밖
+        assert!(is_painted(&img, 112, 100) && !is_painted(&img, 110, 110));
+    }
+
+    #[test]
+    fn nothing_is_drawn_without_cell_or_opacity() {
+        let transparent = Tile { opacity: 0.0, ..logo_tile(0.0, 0.0) };
+        assert!(tiled(&transparent).pixels().all(|pixel| pixel.0 == [255, 255, 255]));
+
+        // 로고도 폰트도 없으면 칸을 만들 수 없음
+        let text_only = Tile { logo: None, text: Some("DRAFT".to_string()), ..logo_tile(0.0, 0.0) };
+        assert!(tiled(&text_only).pixels().all(|pixel| pixel.0 == [255, 255, 255]));
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let invalid = [
+            TileOptions { size_percent: 0.0, ..Default::default() },
+            TileOptions { spacing_percent: -1.0, ..Default::default() },
+            TileOptions { angle: f32::NAN, ..Default::default() },
+            TileOptions { opacity: 1.5, ..Default::default() },
+            TileOptions { stagger: 2.0, ..Default::default() },
+        ];
+        for options in invalid {
+            assert!(options.validate().is_err(), "{:?}", options);
+        }
+        assert!(Tile::load(&TileOptions { color: "white".to_string(), ..Default::default() }, &LogoCache::new()).is_err());
+        assert!(Tile::load(&TileOptions::default(), &LogoCache::new()).is_ok());
+    }
+}
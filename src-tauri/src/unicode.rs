@@ -0,0 +1,43 @@
+// 텍스트 / 파일명 유니코드 정규화 (NFC)
+//
+// macOS 에서 만든 파일명은 한글이 자모로 분해된 NFD ("한" → ᄒ ᅡ ᆫ) 로 들어오는 경우가 많아서
+// 파일명을 그대로 텍스트로 쓰면 음절 대신 자모가 따로 그려지고 너비 추정도 틀어집니다.
+// 측정하고 그리기 전에 NFC 로 합치고 (첫가끝 조합형 자모 U+1100 ~ U+11FF 도 음절로 합쳐짐),
+// 출력 파일을 찾을 때는 정규화 형태만 다른 이름을 같은 이름으로 봅니다.
+
+use std::borrow::Cow;
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+// 이미 NFC 면 복사하지 않음
+pub fn to_nfc(text: &str) -> Cow<'_, str> {
+    match is_nfc_quick(text.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(text),
+        _ => Cow::Owned(text.nfc().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nfd_hangul_is_composed() {
+        let nfd = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}";
+        assert_eq!(to_nfc(nfd), "한글");
+        assert!(matches!(to_nfc(nfd), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn conjoining_jamo_become_syllables() {
+        // 첫가끝 조합형 자모 (U+1100 초성, U+1161 중성) → "가"
+        assert_eq!(to_nfc("\u{1100}\u{1161}"), "가");
+        assert_eq!(to_nfc("\u{1100}\u{1161}.jpg").chars().count(), 5);
+    }
+
+    #[test]
+    fn nfc_text_is_borrowed() {
+        for text in ["", "hello.jpg", "한글 텍스트", "café"] {
+            assert!(matches!(to_nfc(text), Cow::Borrowed(borrowed) if borrowed == text), "{:?}", text);
+        }
+    }
+}
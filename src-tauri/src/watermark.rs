@@ -0,0 +1,319 @@
+// 로고 워터마크 (PNG / WebP / SVG)
+//
+// 로고 파일은 한 번만 읽어 두고, 이미지마다 이미지 너비에 대한 비율로 크기를 정해
+// 텍스트와 같은 기준점 + 여백 방식으로 놓은 뒤 불투명도를 곱해 합성합니다.
+// SVG 는 이미지마다 필요한 크기로 다시 그려서 큰 사진에서도 선명합니다.
+// SVG 안의 <text> 는 그리지 않으므로 (폰트를 불러오지 않음) 글자는 윤곽선으로 바꿔 두어야 합니다.
+
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, RgbaImage};
+use resvg::{tiny_skia, usvg};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::layout::TextBounds;
+use crate::placement::{PlacementOptions, TextPosition};
+use crate::render;
+
+// 기본 로고 너비: 이미지 너비의 20%
+const DEFAULT_SIZE_PERCENT: f32 = 20.0;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WatermarkOptions {
+    // 로고 파일 경로 (PNG, WebP, SVG, 그 밖의 이미지는 불투명하게 합성)
+    pub path: String,
+    // 로고 너비 (이미지 너비에 대한 %, 높이는 로고 비율대로)
+    #[serde(default = "default_size_percent")]
+    pub size_percent: f32,
+    // 0.0 (투명) ~ 1.0 (원본 알파 그대로)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    // 기준점과 여백 (텍스트 배치와 같은 형식, 기본 오른쪽 아래)
+    #[serde(default)]
+    pub placement: PlacementOptions,
+}
+
+fn default_size_percent() -> f32 {
+    DEFAULT_SIZE_PERCENT
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+impl WatermarkOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.path.is_empty() {
+            return Err("워터마크 파일이 지정되지 않았습니다.".to_string());
+        }
+        if !(self.size_percent.is_finite() && self.size_percent > 0.0 && self.size_percent <= 100.0) {
+            return Err(format!("워터마크 크기는 0 ~ 100% 사이여야 합니다: {}", self.size_percent));
+        }
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err(format!("워터마크 불투명도는 0 ~ 1 사이여야 합니다: {}", self.opacity));
+        }
+        self.placement.validate()
+    }
+}
+
+// 읽어 둔 로고 파일 (반복 워터마크에서도 사용)
+pub enum Logo {
+    // 알파를 미리 곱한 RGBA (크기를 줄일 때 투명한 가장자리가 어두워지지 않도록)
+    Raster(RgbaImage),
+    Svg(Box<usvg::Tree>),
+}
+
+impl Logo {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let data = fs::read(path).map_err(|e| format!("워터마크 파일 읽기 실패: {}", e))?;
+
+        if is_svg(Path::new(path), &data) {
+            let tree = usvg::Tree::from_data(&data, &usvg::Options::default())
+                .map_err(|e| format!("SVG 워터마크를 읽을 수 없습니다: {}", e))?;
+            Ok(Logo::Svg(Box::new(tree)))
+        } else {
+            let img = crate::decode_image(&data).map_err(|e| format!("워터마크 이미지 로드 실패: {}", e))?;
+            Ok(Logo::Raster(premultiply(img.to_rgba8())))
+        }
+    }
+
+    // 너비 width 에 맞춘 로고 (높이는 로고 비율대로, 알파를 곱한 RGBA)
+    pub fn render(&self, width: u32) -> Option<RgbaImage> {
+        let width = width.max(1) as f32;
+        match self {
+            Logo::Raster(logo) => {
+                if logo.width() == 0 || logo.height() == 0 {
+                    return None;
+                }
+                let height = (width * logo.height() as f32 / logo.width() as f32).round().max(1.0);
+                Some(image::imageops::resize(logo, width as u32, height as u32, FilterType::Lanczos3))
+            }
+            Logo::Svg(tree) => {
+                let size = tree.size();
+                let scale = width / size.width();
+                let height = (size.height() * scale).round().max(1.0);
+                let mut pixmap = tiny_skia::Pixmap::new(width as u32, height as u32)?;
+                resvg::render(tree, tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+                // tiny-skia 픽셀도 알파를 곱한 RGBA
+                RgbaImage::from_raw(width as u32, height as u32, pixmap.take())
+            }
+        }
+    }
+}
+
+// 읽어 둔 로고 캐시 (Tauri State 로 공유)
+//
+// 미리보기는 설정을 바꿀 때마다 호출되므로 같은 로고를 매번 다시 읽고 디코딩하지 않도록
+// 경로별로 보관합니다. 파일을 고쳐 저장하면 (수정 시각이 바뀌면) 다시 읽습니다.
+#[derive(Default)]
+pub struct LogoCache {
+    logos: Mutex<HashMap<String, CachedLogo>>,
+}
+
+struct CachedLogo {
+    // 읽을 때의 파일 수정 시각 (알 수 없으면 캐시하지 않은 것처럼 매번 다시 읽음)
+    modified: Option<SystemTime>,
+    logo: Arc<Logo>,
+}
+
+impl LogoCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(&self, path: &str) -> Result<Arc<Logo>, String> {
+        let modified = fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        if let Some(cached) = self.lock().get(path) {
+            if modified.is_some() && cached.modified == modified {
+                return Ok(cached.logo.clone());
+            }
+        }
+
+        let logo = Arc::new(Logo::load(path)?);
+        self.lock().insert(path.to_string(), CachedLogo { modified, logo: logo.clone() });
+        Ok(logo)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedLogo>> {
+        // 다른 스레드가 패닉해도 캐시 자체는 유효하므로 계속 사용
+        self.logos.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// 읽어 둔 로고 + 배치 설정 (일괄 처리에서는 한 번 읽어 모든 작업 스레드가 공유)
+pub struct Watermark {
+    logo: Arc<Logo>,
+    size_percent: f32,
+    opacity: f32,
+    placement: PlacementOptions,
+}
+
+impl Watermark {
+    pub fn load(options: &WatermarkOptions, logos: &LogoCache) -> Result<Self, String> {
+        options.validate()?;
+        Ok(Watermark {
+            logo: logos.load(&options.path)?,
+            size_percent: options.size_percent,
+            opacity: options.opacity,
+            placement: options.placement,
+        })
+    }
+
+    // 이미지 크기에 맞춘 로고를 기준점 위치에 합성
+    // margin_factor: 미리보기로 줄인 이미지에 그릴 때 px 여백에 곱할 비율 (퍼센트 여백은 그대로)
+    pub fn apply(&self, img: &mut DynamicImage, margin_factor: f32) {
+        if self.opacity <= 0.0 {
+            return;
+        }
+        let (img_width, img_height) = img.dimensions();
+        let width = (img_width as f32 * self.size_percent / 100.0).round() as u32;
+        let Some(logo) = self.logo.render(width).map(demultiply) else {
+            return;
+        };
+
+        let bounds = TextBounds { x: 0, y: 0, width: logo.width(), height: logo.height() };
+        let (x, y) = TextPosition::Anchored(self.placement)
+            .scaled(margin_factor)
+            .origin(img_width, img_height, &bounds);
+        render::draw_image(img, &logo, x, y, self.opacity);
+    }
+}
+
+// 확장자가 .svg / .svgz 이거나 내용이 XML 로 시작하면 SVG (압축된 svgz 는 usvg 가 풂)
+fn is_svg(path: &Path, data: &[u8]) -> bool {
+    let svg_extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("svg") || e.eq_ignore_ascii_case("svgz"));
+    let text = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    svg_extension || text.trim_ascii_start().starts_with(b"<")
+}
+
+pub fn premultiply(mut img: RgbaImage) -> RgbaImage {
+    for pixel in img.pixels_mut() {
+        let alpha = pixel.0[3] as u32;
+        for c in &mut pixel.0[..3] {
+            *c = ((*c as u32 * alpha + 127) / 255) as u8;
+        }
+    }
+    img
+}
+
+pub fn demultiply(mut img: RgbaImage) -> RgbaImage {
+    for pixel in img.pixels_mut() {
+        let alpha = pixel.0[3] as u32;
+        if alpha == 0 {
+            continue;
+        }
+        for c in &mut pixel.0[..3] {
+            *c = ((*c as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+        }
+    }
+    img
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::placement::Anchor;
+    use image::{Rgb, RgbImage, Rgba};
+
+    fn red_logo(width: u32, height: u32) -> Arc<Logo> {
+        Arc::new(Logo::Raster(premultiply(RgbaImage::from_pixel(width, height, Rgba([255, 0, 0, 255])))))
+    }
+
+    // 흰 바탕에서 색이 바뀐 픽셀의 개수와 영역 (왼쪽, 위, 오른쪽 끝 + 1, 아래쪽 끝 + 1)
+    fn painted_area(img: &DynamicImage) -> (usize, Option<(u32, u32, u32, u32)>) {
+        let painted: Vec<(u32, u32)> = img
+            .to_rgb8()
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| pixel.0 != [255, 255, 255])
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        let area = painted.iter().fold(None, |area: Option<(u32, u32, u32, u32)>, &(x, y)| {
+            Some(match area {
+                Some((left, top, right, bottom)) => (left.min(x), top.min(y), right.max(x + 1), bottom.max(y + 1)),
+                None => (x, y, x + 1, y + 1),
+            })
+        });
+        (painted.len(), area)
+    }
+
+    #[test]
+    fn premultiply_round_trip() {
+        let original = RgbaImage::from_fn(4, 1, |x, _| match x {
+            0 => Rgba([200, 100, 50, 255]),
+            1 => Rgba([200, 100, 50, 0]),
+            2 => Rgba([0, 0, 0, 0]),
+            _ => Rgba([200, 100, 50, 128]),
+        });
+        let premultiplied = premultiply(original.clone());
+        assert_eq!(premultiplied.get_pixel(0, 0), original.get_pixel(0, 0));
+        // 완전히 투명한 픽셀은 색이 0 이 되고 되돌려도 0
+        assert_eq!(premultiplied.get_pixel(1, 0).0, [0, 0, 0, 0]);
+        assert_eq!(premultiplied.get_pixel(3, 0).0, [100, 50, 25, 128]);
+
+        let restored = demultiply(premultiplied);
+        assert_eq!(restored.get_pixel(0, 0), original.get_pixel(0, 0));
+        assert_eq!(restored.get_pixel(1, 0).0, [0, 0, 0, 0]);
+        assert_eq!(restored.get_pixel(2, 0).0, [0, 0, 0, 0]);
+        // 반투명은 반올림 오차 1 이내
+        for (restored, original) in restored.get_pixel(3, 0).0.iter().zip(original.get_pixel(3, 0).0) {
+            assert!(restored.abs_diff(original) <= 1, "{} {}", restored, original);
+        }
+    }
+
+    #[test]
+    fn logo_keeps_aspect_ratio() {
+        let logo = red_logo(10, 5);
+        assert_eq!(logo.render(50).unwrap().dimensions(), (50, 25));
+        assert_eq!(logo.render(1).unwrap().dimensions(), (1, 1));
+
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="20" height="10" fill="red"/></svg>"#;
+        let tree = usvg::Tree::from_data(svg, &usvg::Options::default()).unwrap();
+        let rendered = Logo::Svg(Box::new(tree)).render(40).unwrap();
+        assert_eq!(rendered.dimensions(), (40, 20));
+        assert_eq!(rendered.get_pixel(20, 10).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn logo_width_follows_size_percent() {
+        let watermark = |anchor, margin_x, margin_y| Watermark {
+            logo: red_logo(10, 5),
+            size_percent: 25.0,
+            opacity: 1.0,
+            placement: PlacementOptions { anchor, margin_x, margin_y, ..Default::default() },
+        };
+
+        // 200x100 이미지의 25% → 50x25 로고
+        let mut img = DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 100, Rgb([255, 255, 255])));
+        watermark(Anchor::BottomRight, 0.0, 0.0).apply(&mut img, 1.0);
+        assert_eq!(painted_area(&img), (50 * 25, Some((150, 75, 200, 100))));
+
+        // 미리보기 (절반 크기) 에서는 px 여백도 절반
+        let mut img = DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 100, Rgb([255, 255, 255])));
+        watermark(Anchor::TopLeft, 20.0, 10.0).apply(&mut img, 0.5);
+        assert_eq!(painted_area(&img), (50 * 25, Some((10, 5, 60, 30))));
+    }
+
+    #[test]
+    fn transparent_watermark_draws_nothing() {
+        let mut img = DynamicImage::ImageRgb8(RgbImage::from_pixel(100, 100, Rgb([255, 255, 255])));
+        let watermark = Watermark { logo: red_logo(10, 10), size_percent: 50.0, opacity: 0.0, placement: PlacementOptions::default() };
+        watermark.apply(&mut img, 1.0);
+        assert_eq!(painted_area(&img), (0, None));
+    }
+
+    #[test]
+    fn detects_svg_by_extension_or_content() {
+        assert!(is_svg(Path::new("logo.SVG"), b""));
+        assert!(is_svg(Path::new("logo.svgz"), b"\x1f\x8b"));
+        assert!(is_svg(Path::new("logo"), b"\xEF\xBB\xBF  <?xml version=\"1.0\"?><svg/>"));
+        assert!(!is_svg(Path::new("logo.png"), b"\x89PNG\r\n"));
+    }
+}
@@ -1,4 +1,4 @@
-import { dialog, invoke } from '@tauri-apps/api';
+import { dialog, event, invoke } from '@tauri-apps/api';
 
 // 비밀번호 생성 및 검증 클래스
 class PasswordManager {
@@ -196,10 +196,21 @@ class ImageOverlayApp {
     this.selectedImageIndex = 0;
     this.selectedImageIndexForPreview = 0; // 개별 미리보기용 인덱스
     this.currentSettings = {
-      fontSize: 20,
-      positionX: 10, // 🔧 우측에서 10% 떨어진 위치
-      positionY: 10, // 🔧 하단에서 10% 떨어진 위치
-      text: ''
+      fontSize: 5,
+      fontSizeMode: localStorage.getItem('imageOverlayFontSizeMode') || 'percentShorterSide', // 크기 기준 (기본: 이미지 짧은 변의 5%)
+      anchor: localStorage.getItem('imageOverlayAnchor') || 'bottom-right', // 텍스트 기준점 (9방향)
+      positionX: 10, // 🔧 기준점 쪽 가장자리에서 가로 10% 떨어진 위치
+      positionY: 10, // 🔧 기준점 쪽 가장자리에서 세로 10% 떨어진 위치
+      text: '',
+      fontId: localStorage.getItem('imageOverlayFontId') || '', // 빈 값이면 기본 폰트
+      overwrite: localStorage.getItem('imageOverlayOverwrite') || 'overwrite', // 같은 이름의 결과 파일이 있을 때 (overwrite / skip / rename)
+      fileNameTemplate: localStorage.getItem('imageOverlayFileNameTemplate') || '', // 빈 값이면 원본 파일명
+      encoding: this.loadEncoding(), // 저장 형식과 품질
+      metadata: localStorage.getItem('imageOverlayMetadata') || 'keep-all', // 원본 메타데이터 (keep-all / color-profile / strip)
+      scan: this.loadScanSettings(), // 입력 폴더 검색 (하위 폴더, 패턴)
+      style: this.loadTextStyle(),
+      watermark: this.loadWatermark(), // 로고 워터마크 (파일, 크기, 위치, 불투명도)
+      tile: this.loadTile() // 반복 워터마크 (시안용 사선 패턴)
     };
     
     // 성능 최적화를 위한 개선된 디바운싱 (더 짧은 지연시간)
@@ -221,6 +232,7 @@ class ImageOverlayApp {
     
     this.initializeElements();
     this.attachEventListeners();
+    this.loadFonts(localStorage.getItem('imageOverlayFontDir'));
     
     // 로그인 성공 후에만 초기화 메시지 표시
     if (this.loginManager.checkLoginStatus()) {
@@ -233,7 +245,91 @@ class ImageOverlayApp {
     this.inputFolderBtn = document.getElementById('inputFolderBtn');
     this.outputFolderBtn = document.getElementById('outputFolderBtn');
     this.inputFolderPath = document.getElementById('inputFolderPath');
+    this.scanRecursive = document.getElementById('scanRecursive');
+    this.scanMaxDepth = document.getElementById('scanMaxDepth');
+    this.scanHidden = document.getElementById('scanHidden');
+    this.scanSymlinks = document.getElementById('scanSymlinks');
+    this.mirrorSubfolders = document.getElementById('mirrorSubfolders');
+    this.scanInclude = document.getElementById('scanInclude');
+    this.scanExclude = document.getElementById('scanExclude');
+    this.applyScanSettingsToControls();
     this.outputFolderPath = document.getElementById('outputFolderPath');
+    this.overwriteSelect = document.getElementById('overwriteSelect');
+    this.overwriteSelect.value = this.currentSettings.overwrite;
+    this.fileNameTemplate = document.getElementById('fileNameTemplate');
+    this.fileNameTemplate.value = this.currentSettings.fileNameTemplate;
+
+    // 저장 형식
+    this.outputFormat = document.getElementById('outputFormat');
+    this.outputQuality = document.getElementById('outputQuality');
+    this.outputQualityLabel = document.getElementById('outputQualityLabel');
+    this.outputQualityValue = document.getElementById('outputQualityValue');
+    this.jpegOptions = document.getElementById('jpegOptions');
+    this.jpegProgressive = document.getElementById('jpegProgressive');
+    this.chromaSubsampling = document.getElementById('chromaSubsampling');
+    this.pngOptions = document.getElementById('pngOptions');
+    this.pngCompression = document.getElementById('pngCompression');
+    this.pngCompressionValue = document.getElementById('pngCompressionValue');
+    this.webpOptions = document.getElementById('webpOptions');
+    this.webpLossless = document.getElementById('webpLossless');
+    this.applyEncodingToControls();
+    this.metadataSelect = document.getElementById('metadataSelect');
+    this.metadataSelect.value = this.currentSettings.metadata;
+
+    // 폰트 선택
+    this.fontSelect = document.getElementById('fontSelect');
+    this.fontFolderBtn = document.getElementById('fontFolderBtn');
+
+    // 텍스트 스타일
+    this.textColor = document.getElementById('textColor');
+    this.backgroundColor = document.getElementById('backgroundColor');
+    this.backgroundOpacity = document.getElementById('backgroundOpacity');
+    this.backgroundOpacityValue = document.getElementById('backgroundOpacityValue');
+    this.noBackground = document.getElementById('noBackground');
+    this.strokeEnabled = document.getElementById('strokeEnabled');
+    this.strokeColor = document.getElementById('strokeColor');
+    this.strokeWidth = document.getElementById('strokeWidth');
+    this.shadowEnabled = document.getElementById('shadowEnabled');
+    this.glowEnabled = document.getElementById('glowEnabled');
+    this.glowColor = document.getElementById('glowColor');
+    this.textAlign = document.getElementById('textAlign');
+    this.lineHeight = document.getElementById('lineHeight');
+    this.lineHeightValue = document.getElementById('lineHeightValue');
+    this.wrapWidth = document.getElementById('wrapWidth');
+    this.wrapWidthValue = document.getElementById('wrapWidthValue');
+    this.textRotation = document.getElementById('textRotation');
+    this.textRotationValue = document.getElementById('textRotationValue');
+    this.applyTextStyleToControls();
+
+    // 로고 워터마크
+    this.watermarkEnabled = document.getElementById('watermarkEnabled');
+    this.watermarkFileBtn = document.getElementById('watermarkFileBtn');
+    this.watermarkFilePath = document.getElementById('watermarkFilePath');
+    this.watermarkAnchor = document.getElementById('watermarkAnchor');
+    this.watermarkSize = document.getElementById('watermarkSize');
+    this.watermarkSizeValue = document.getElementById('watermarkSizeValue');
+    this.watermarkOpacity = document.getElementById('watermarkOpacity');
+    this.watermarkOpacityValue = document.getElementById('watermarkOpacityValue');
+    this.watermarkMargin = document.getElementById('watermarkMargin');
+    this.watermarkMarginValue = document.getElementById('watermarkMarginValue');
+    this.applyWatermarkToControls();
+
+    // 반복 워터마크
+    this.tileEnabled = document.getElementById('tileEnabled');
+    this.tileContent = document.getElementById('tileContent');
+    this.tileText = document.getElementById('tileText');
+    this.tileColor = document.getElementById('tileColor');
+    this.tileSize = document.getElementById('tileSize');
+    this.tileSizeValue = document.getElementById('tileSizeValue');
+    this.tileSpacing = document.getElementById('tileSpacing');
+    this.tileSpacingValue = document.getElementById('tileSpacingValue');
+    this.tileAngle = document.getElementById('tileAngle');
+    this.tileAngleValue = document.getElementById('tileAngleValue');
+    this.tileOpacity = document.getElementById('tileOpacity');
+    this.tileOpacityValue = document.getElementById('tileOpacityValue');
+    this.tileStagger = document.getElementById('tileStagger');
+    this.tileStaggerValue = document.getElementById('tileStaggerValue');
+    this.applyTileToControls();
 
     // 모드 토글 버튼
     this.toggleBatchBtn = document.getElementById('toggleBatchBtn');
@@ -251,10 +347,14 @@ class ImageOverlayApp {
     // 일괄 처리 슬라이더 요소들
     this.fontSize = document.getElementById('fontSize');
     this.fontSizeValue = document.getElementById('fontSizeValue');
+    this.fontSizeUnit = document.getElementById('fontSizeUnit');
+    this.fontSizeMode = document.getElementById('fontSizeMode');
     this.positionX = document.getElementById('positionX');
     this.posXValue = document.getElementById('posXValue');
     this.positionY = document.getElementById('positionY');
     this.posYValue = document.getElementById('posYValue');
+    this.anchorSelect = document.getElementById('anchorSelect');
+    this.anchorSelect.value = this.currentSettings.anchor;
 
     // 미리보기 요소들
     this.previewImage = document.getElementById('previewImage');
@@ -271,8 +371,13 @@ class ImageOverlayApp {
     // 개별 처리 슬라이더 요소들
     this.indFontSize = document.getElementById('indFontSize');
     this.indFontSizeValue = document.getElementById('indFontSizeValue');
+    this.indFontSizeUnit = document.getElementById('indFontSizeUnit');
+    this.indFontSizeMode = document.getElementById('indFontSizeMode');
+    this.applyFontSizeModeToControls();
     this.indPositionX = document.getElementById('indPositionX');
     this.indPosXValue = document.getElementById('indPosXValue');
+    this.indAnchorSelect = document.getElementById('indAnchorSelect');
+    this.indAnchorSelect.value = this.currentSettings.anchor;
     this.indPositionY = document.getElementById('indPositionY');
     this.indPosYValue = document.getElementById('indPosYValue');
 
@@ -289,6 +394,14 @@ class ImageOverlayApp {
     this.logContent = document.getElementById('logContent');
     this.confirmLogBtn = document.getElementById('confirmLogBtn');
 
+    // 진행 상황 모달 요소들
+    this.progressModal = document.getElementById('progressModal');
+    this.progressFill = document.getElementById('progressFill');
+    this.progressCounts = document.getElementById('progressCounts');
+    this.progressEta = document.getElementById('progressEta');
+    this.progressCurrent = document.getElementById('progressCurrent');
+    this.stopBatchBtn = document.getElementById('stopBatchBtn');
+
     // 알림 요소
     this.notification = document.getElementById('notification');
 
@@ -335,7 +448,7 @@ class ImageOverlayApp {
 
   // 미리보기 캐시 관리
   getCacheKey(imagePath, text, fontSize, posX, posY) {
-    return `${imagePath}_${text}_${fontSize}_${posX}_${posY}`;
+    return `${imagePath}_${text}_${this.currentSettings.fontSizeMode}_${fontSize}_${this.currentSettings.anchor}_${posX}_${posY}_${this.currentSettings.fontId}_${JSON.stringify(this.currentSettings.style)}_${JSON.stringify(this.watermarkOptions())}_${JSON.stringify(this.tileOptions())}`;
   }
 
   addToCache(key, data) {
@@ -473,7 +586,59 @@ class ImageOverlayApp {
   attachEventListeners() {
     // 폴더 선택 이벤트
     this.inputFolderBtn.addEventListener('click', () => this.selectInputFolder());
+    [this.scanRecursive, this.scanMaxDepth, this.scanHidden, this.scanSymlinks, this.scanInclude, this.scanExclude].forEach(control => {
+      control.addEventListener('change', () => this.updateScanSettings(true));
+    });
+    this.mirrorSubfolders.addEventListener('change', () => this.updateScanSettings(false));
     this.outputFolderBtn.addEventListener('click', () => this.selectOutputFolder());
+    this.overwriteSelect.addEventListener('change', (e) => {
+      this.currentSettings.overwrite = e.target.value;
+      localStorage.setItem('imageOverlayOverwrite', e.target.value);
+    });
+    this.fileNameTemplate.addEventListener('change', (e) => {
+      this.currentSettings.fileNameTemplate = e.target.value.trim();
+      localStorage.setItem('imageOverlayFileNameTemplate', this.currentSettings.fileNameTemplate);
+    });
+    [this.outputFormat, this.chromaSubsampling].forEach(control => {
+      control.addEventListener('change', () => this.updateEncoding());
+    });
+    [this.outputQuality, this.pngCompression].forEach(control => {
+      control.addEventListener('input', () => this.updateEncoding());
+    });
+    [this.jpegProgressive, this.webpLossless].forEach(control => {
+      control.addEventListener('change', () => this.updateEncoding());
+    });
+    this.metadataSelect.addEventListener('change', (e) => {
+      this.currentSettings.metadata = e.target.value;
+      localStorage.setItem('imageOverlayMetadata', e.target.value);
+    });
+
+    // 폰트 선택 이벤트
+    this.fontFolderBtn.addEventListener('click', () => this.selectFontFolder());
+    this.fontSelect.addEventListener('change', (e) => {
+      this.currentSettings.fontId = e.target.value;
+      localStorage.setItem('imageOverlayFontId', e.target.value);
+      this.clearCache();
+    });
+
+    // 텍스트 스타일 이벤트
+    [this.textColor, this.backgroundColor, this.backgroundOpacity, this.noBackground,
+     this.strokeEnabled, this.strokeColor, this.strokeWidth, this.shadowEnabled, this.glowEnabled, this.glowColor,
+     this.textAlign, this.lineHeight, this.wrapWidth, this.textRotation].forEach(control => {
+      control.addEventListener('input', () => this.updateTextStyle());
+    });
+
+    // 로고 워터마크 이벤트
+    this.watermarkFileBtn.addEventListener('click', () => this.selectWatermarkFile());
+    [this.watermarkEnabled, this.watermarkAnchor, this.watermarkSize, this.watermarkOpacity, this.watermarkMargin].forEach(control => {
+      control.addEventListener('input', () => this.updateWatermark());
+    });
+
+    // 반복 워터마크 이벤트
+    [this.tileEnabled, this.tileContent, this.tileText, this.tileColor, this.tileSize, this.tileSpacing,
+     this.tileAngle, this.tileOpacity, this.tileStagger].forEach(control => {
+      control.addEventListener('input', () => this.updateTile());
+    });
 
     // 모드 토글 이벤트
     this.toggleBatchBtn.addEventListener('click', () => this.toggleMode(true));
@@ -521,6 +686,29 @@ class ImageOverlayApp {
       this.updateIndividualPreviewDebounced(); // 디바운싱 사용
     });
 
+    // 크기 기준 변경 (일괄/개별 모달이 같은 값을 공유)
+    [this.fontSizeMode, this.indFontSizeMode].forEach(select => {
+      select.addEventListener('change', (e) => {
+        this.currentSettings.fontSizeMode = e.target.value;
+        localStorage.setItem('imageOverlayFontSizeMode', e.target.value);
+        this.applyFontSizeModeToControls();
+        this.updatePreviewDebounced();
+        this.updateIndividualPreviewDebounced();
+      });
+    });
+
+    // 기준점 변경 (일괄/개별 모달이 같은 값을 공유)
+    [this.anchorSelect, this.indAnchorSelect].forEach(select => {
+      select.addEventListener('change', (e) => {
+        this.currentSettings.anchor = e.target.value;
+        this.anchorSelect.value = e.target.value;
+        this.indAnchorSelect.value = e.target.value;
+        localStorage.setItem('imageOverlayAnchor', e.target.value);
+        this.updatePreviewDebounced();
+        this.updateIndividualPreviewDebounced();
+      });
+    });
+
     this.indPositionX.addEventListener('input', (e) => {
       // 🔧 수정: 슬라이더 값을 그대로 사용 (우측에서의 거리)
       this.indPosXValue.textContent = e.target.value; // 우측에서 e.target.value% 떨어진 지점
@@ -540,6 +728,7 @@ class ImageOverlayApp {
     // 로그 모달 이벤트
     this.closeLogModal.addEventListener('click', () => this.closeLogModalHandler());
     this.confirmLogBtn.addEventListener('click', () => this.closeLogModalHandler());
+    this.stopBatchBtn.addEventListener('click', () => this.cancelBatch());
 
     // 커스텀 텍스트 입력 이벤트 (한글 정규화 추가)
     this.customText.addEventListener('input', (e) => {
@@ -611,10 +800,405 @@ class ImageOverlayApp {
     }
   }
 
+  // 저장된 텍스트 스타일 불러오기 (없으면 기본값: 빨간 글자 + 흰 배경)
+  loadTextStyle() {
+    const defaults = {
+      textColor: '#ff0000', backgroundColor: '#ffffff', backgroundOpacity: 0.96, noBackground: false,
+      strokeEnabled: false, strokeColor: '#000000', strokeWidth: 2,
+      shadowEnabled: false,
+      glowEnabled: false, glowColor: '#ffffff',
+      align: 'left', lineHeight: 1.0, wrapPercent: 0, rotation: 0
+    };
+    try {
+      return { ...defaults, ...JSON.parse(localStorage.getItem('imageOverlayStyle') || '{}') };
+    } catch (error) {
+      console.warn('텍스트 스타일 불러오기 실패:', error);
+      return defaults;
+    }
+  }
+
+  applyTextStyleToControls() {
+    const style = this.currentSettings.style;
+    this.textColor.value = style.textColor;
+    this.backgroundColor.value = style.backgroundColor;
+    this.backgroundOpacity.value = Math.round(style.backgroundOpacity * 100);
+    this.backgroundOpacityValue.textContent = this.backgroundOpacity.value;
+    this.noBackground.checked = style.noBackground;
+    this.backgroundColor.disabled = style.noBackground;
+    this.backgroundOpacity.disabled = style.noBackground;
+    this.strokeEnabled.checked = style.strokeEnabled;
+    this.strokeColor.value = style.strokeColor;
+    this.strokeWidth.value = style.strokeWidth;
+    this.strokeColor.disabled = !style.strokeEnabled;
+    this.strokeWidth.disabled = !style.strokeEnabled;
+    this.shadowEnabled.checked = style.shadowEnabled;
+    this.glowEnabled.checked = style.glowEnabled;
+    this.glowColor.value = style.glowColor;
+    this.glowColor.disabled = !style.glowEnabled;
+    this.textAlign.value = style.align;
+    this.lineHeight.value = style.lineHeight;
+    this.lineHeightValue.textContent = style.lineHeight.toFixed(1);
+    this.wrapWidth.value = style.wrapPercent;
+    this.wrapWidthValue.textContent = style.wrapPercent > 0 ? `이미지 너비의 ${style.wrapPercent}%` : '사용 안 함';
+    this.textRotation.value = style.rotation;
+    this.textRotationValue.textContent = style.rotation;
+  }
+
+  updateTextStyle() {
+    this.currentSettings.style = {
+      textColor: this.textColor.value,
+      backgroundColor: this.backgroundColor.value,
+      backgroundOpacity: parseInt(this.backgroundOpacity.value) / 100,
+      noBackground: this.noBackground.checked,
+      strokeEnabled: this.strokeEnabled.checked,
+      strokeColor: this.strokeColor.value,
+      strokeWidth: Math.min(50, Math.max(1, parseInt(this.strokeWidth.value) || 2)),
+      shadowEnabled: this.shadowEnabled.checked,
+      glowEnabled: this.glowEnabled.checked,
+      glowColor: this.glowColor.value,
+      align: this.textAlign.value,
+      lineHeight: parseFloat(this.lineHeight.value) || 1.0,
+      wrapPercent: parseInt(this.wrapWidth.value) || 0,
+      rotation: Math.min(180, Math.max(-180, parseInt(this.textRotation.value) || 0))
+    };
+    localStorage.setItem('imageOverlayStyle', JSON.stringify(this.currentSettings.style));
+    this.applyTextStyleToControls();
+    this.clearCache();
+  }
+
+  // 저장된 로고 워터마크 설정 불러오기 (없으면 사용 안 함, 오른쪽 아래에 이미지 너비의 20%)
+  loadWatermark() {
+    const defaults = { enabled: false, path: '', anchor: 'bottom-right', sizePercent: 20, opacity: 1.0, marginPercent: 3 };
+    try {
+      return { ...defaults, ...JSON.parse(localStorage.getItem('imageOverlayWatermark') || '{}') };
+    } catch (error) {
+      console.warn('로고 워터마크 설정 불러오기 실패:', error);
+      return defaults;
+    }
+  }
+
+  applyWatermarkToControls() {
+    const watermark = this.currentSettings.watermark;
+    this.watermarkEnabled.checked = watermark.enabled;
+    this.watermarkFilePath.textContent = watermark.path || '선택된 파일 없음';
+    this.watermarkAnchor.value = watermark.anchor;
+    this.watermarkSize.value = watermark.sizePercent;
+    this.watermarkSizeValue.textContent = watermark.sizePercent;
+    this.watermarkOpacity.value = Math.round(watermark.opacity * 100);
+    this.watermarkOpacityValue.textContent = this.watermarkOpacity.value;
+    this.watermarkMargin.value = watermark.marginPercent;
+    this.watermarkMarginValue.textContent = watermark.marginPercent;
+    [this.watermarkAnchor, this.watermarkSize, this.watermarkOpacity, this.watermarkMargin].forEach(control => {
+      control.disabled = !watermark.enabled;
+    });
+  }
+
+  updateWatermark() {
+    this.currentSettings.watermark = {
+      ...this.currentSettings.watermark,
+      enabled: this.watermarkEnabled.checked,
+      anchor: this.watermarkAnchor.value,
+      sizePercent: Math.min(100, Math.max(1, parseInt(this.watermarkSize.value) || 20)),
+      opacity: parseInt(this.watermarkOpacity.value) / 100,
+      marginPercent: Math.min(50, Math.max(0, parseInt(this.watermarkMargin.value) || 0))
+    };
+    localStorage.setItem('imageOverlayWatermark', JSON.stringify(this.currentSettings.watermark));
+    this.applyWatermarkToControls();
+    this.clearCache();
+  }
+
+  async selectWatermarkFile() {
+    try {
+      const result = await dialog.open({
+        multiple: false,
+        title: '로고 파일 선택',
+        filters: [{ name: '로고 이미지', extensions: ['png', 'webp', 'svg'] }]
+      });
+
+      if (result) {
+        this.currentSettings.watermark.path = result;
+        this.watermarkEnabled.checked = true;
+        this.updateWatermark();
+        this.showNotification('로고 파일이 선택되었습니다.', 'success');
+      }
+    } catch (error) {
+      console.error('로고 파일 선택 오류:', error);
+      this.showNotification('파일 선택 중 오류가 발생했습니다.', 'error');
+    }
+  }
+
+  // 저장된 반복 워터마크 설정 불러오기 (없으면 사용 안 함, 30° 기울인 흰 글자 25%)
+  loadTile() {
+    const defaults = {
+      enabled: false, content: 'text', text: '', color: '#ffffff',
+      sizePercent: 5, spacingPercent: 8, angle: 30, opacity: 0.25, stagger: 0.5
+    };
+    try {
+      return { ...defaults, ...JSON.parse(localStorage.getItem('imageOverlayTile') || '{}') };
+    } catch (error) {
+      console.warn('반복 워터마크 설정 불러오기 실패:', error);
+      return defaults;
+    }
+  }
+
+  applyTileToControls() {
+    const tile = this.currentSettings.tile;
+    this.tileEnabled.checked = tile.enabled;
+    this.tileContent.value = tile.content;
+    this.tileText.value = tile.text;
+    this.tileColor.value = tile.color;
+    this.tileSize.value = tile.sizePercent;
+    this.tileSizeValue.textContent = tile.sizePercent;
+    this.tileSpacing.value = tile.spacingPercent;
+    this.tileSpacingValue.textContent = tile.spacingPercent;
+    this.tileAngle.value = tile.angle;
+    this.tileAngleValue.textContent = tile.angle;
+    this.tileOpacity.value = Math.round(tile.opacity * 100);
+    this.tileOpacityValue.textContent = this.tileOpacity.value;
+    this.tileStagger.value = Math.round(tile.stagger * 100);
+    this.tileStaggerValue.textContent = this.tileStagger.value;
+    [this.tileContent, this.tileSize, this.tileSpacing, this.tileAngle, this.tileOpacity, this.tileStagger].forEach(control => {
+      control.disabled = !tile.enabled;
+    });
+    // 로고를 반복할 때는 텍스트와 색상을 쓰지 않음
+    this.tileText.disabled = !tile.enabled || tile.content === 'logo';
+    this.tileColor.disabled = !tile.enabled || tile.content === 'logo';
+  }
+
+  updateTile() {
+    this.currentSettings.tile = {
+      enabled: this.tileEnabled.checked,
+      content: this.tileContent.value,
+      text: this.tileText.value,
+      color: this.tileColor.value,
+      sizePercent: Math.min(30, Math.max(1, parseInt(this.tileSize.value) || 5)),
+      spacingPercent: Math.min(50, Math.max(0, parseInt(this.tileSpacing.value) || 0)),
+      angle: Math.min(90, Math.max(-90, parseInt(this.tileAngle.value) || 0)),
+      opacity: parseInt(this.tileOpacity.value) / 100,
+      stagger: parseInt(this.tileStagger.value) / 100
+    };
+    localStorage.setItem('imageOverlayTile', JSON.stringify(this.currentSettings.tile));
+    this.applyTileToControls();
+    this.clearCache();
+  }
+
+  // Rust 의 TileOptions 형식으로 변환 (사용 안 함이거나 로고 파일이 없으면 null)
+  tileOptions() {
+    const tile = this.currentSettings.tile;
+    const logo = tile.content === 'logo' ? this.currentSettings.watermark.path : null;
+    if (!tile.enabled || (tile.content === 'logo' && !logo)) {
+      return null;
+    }
+    return {
+      text: tile.content === 'text' && tile.text.trim() ? tile.text : null,
+      logo,
+      color: tile.color,
+      sizePercent: tile.sizePercent,
+      spacingPercent: tile.spacingPercent,
+      angle: tile.angle,
+      opacity: tile.opacity,
+      stagger: tile.stagger
+    };
+  }
+
+  // Rust 의 WatermarkOptions 형식으로 변환 (사용 안 함이거나 파일이 없으면 null)
+  watermarkOptions() {
+    const watermark = this.currentSettings.watermark;
+    if (!watermark.enabled || !watermark.path) {
+      return null;
+    }
+    return {
+      path: watermark.path,
+      sizePercent: watermark.sizePercent,
+      opacity: watermark.opacity,
+      placement: {
+        anchor: watermark.anchor,
+        marginX: watermark.marginPercent,
+        marginY: watermark.marginPercent,
+        marginUnit: 'percent'
+      }
+    };
+  }
+
+  // 저장된 폴더 검색 설정 불러오기 (없으면 기존처럼 입력 폴더 바로 아래만)
+  loadScanSettings() {
+    const defaults = {
+      recursive: false, maxDepth: null, includeHidden: false, symlinks: 'files',
+      include: '', exclude: '', mirrorSubfolders: true
+    };
+    try {
+      return { ...defaults, ...JSON.parse(localStorage.getItem('imageOverlayScan') || '{}') };
+    } catch (error) {
+      console.warn('폴더 검색 설정 불러오기 실패:', error);
+      return defaults;
+    }
+  }
+
+  applyScanSettingsToControls() {
+    const scan = this.currentSettings.scan;
+    this.scanRecursive.checked = scan.recursive;
+    this.scanMaxDepth.value = scan.maxDepth ?? '';
+    this.scanMaxDepth.disabled = !scan.recursive;
+    this.scanHidden.checked = scan.includeHidden;
+    this.scanSymlinks.value = scan.symlinks;
+    this.mirrorSubfolders.checked = scan.mirrorSubfolders;
+    this.mirrorSubfolders.disabled = !scan.recursive;
+    this.scanInclude.value = scan.include;
+    this.scanExclude.value = scan.exclude;
+  }
+
+  // rescan: 검색 조건이 바뀌었으면 입력 폴더를 다시 읽음
+  async updateScanSettings(rescan) {
+    const maxDepth = parseInt(this.scanMaxDepth.value);
+    this.currentSettings.scan = {
+      recursive: this.scanRecursive.checked,
+      maxDepth: maxDepth > 0 ? maxDepth : null,
+      includeHidden: this.scanHidden.checked,
+      symlinks: this.scanSymlinks.value,
+      include: this.scanInclude.value.trim(),
+      exclude: this.scanExclude.value.trim(),
+      mirrorSubfolders: this.mirrorSubfolders.checked
+    };
+    localStorage.setItem('imageOverlayScan', JSON.stringify(this.currentSettings.scan));
+    this.applyScanSettingsToControls();
+
+    if (rescan && this.inputPath) {
+      await this.loadImages();
+    }
+  }
+
+  // Rust 의 ScanOptions 형식으로 변환 (패턴은 쉼표로 구분)
+  scanOptions() {
+    const scan = this.currentSettings.scan;
+    const patterns = (value) => value.split(',').map(pattern => pattern.trim()).filter(Boolean);
+    return {
+      recursive: scan.recursive,
+      maxDepth: scan.maxDepth,
+      include: patterns(scan.include),
+      exclude: patterns(scan.exclude),
+      includeHidden: scan.includeHidden,
+      symlinks: scan.symlinks
+    };
+  }
+
+  // 출력 폴더 아래 하위 폴더 (하위 폴더 구조 유지를 켰을 때만)
+  jobSubfolder(image) {
+    const scan = this.currentSettings.scan;
+    return scan.recursive && scan.mirrorSubfolders ? image.subfolder : null;
+  }
+
+  // 저장된 저장 형식 불러오기 (없으면 원본 형식, 기존과 같은 JPEG 품질 90)
+  loadEncoding() {
+    const defaults = {
+      format: 'original', quality: 90, progressive: false, chromaSubsampling: '4:4:4',
+      pngCompression: 6, webpLossless: false
+    };
+    try {
+      return { ...defaults, ...JSON.parse(localStorage.getItem('imageOverlayEncoding') || '{}') };
+    } catch (error) {
+      console.warn('저장 형식 불러오기 실패:', error);
+      return defaults;
+    }
+  }
+
+  // 선택한 형식에 해당하는 옵션만 표시 (원본 형식이면 입력 형식이 섞일 수 있으므로 모두 표시)
+  applyEncodingToControls() {
+    const encoding = this.currentSettings.encoding;
+    const format = encoding.format;
+    const shows = (...formats) => format === 'original' || formats.includes(format);
+    this.outputFormat.value = format;
+    this.outputQuality.value = encoding.quality;
+    this.outputQualityValue.textContent = encoding.quality;
+    this.jpegProgressive.checked = encoding.progressive;
+    this.chromaSubsampling.value = encoding.chromaSubsampling;
+    this.pngCompression.value = encoding.pngCompression;
+    this.pngCompressionValue.textContent = encoding.pngCompression;
+    this.webpLossless.checked = encoding.webpLossless;
+
+    const lossy = shows('jpeg', 'avif') || (shows('webp') && !encoding.webpLossless);
+    this.outputQuality.style.display = lossy ? '' : 'none';
+    this.outputQualityLabel.style.display = lossy ? '' : 'none';
+    this.jpegOptions.style.display = shows('jpeg') ? '' : 'none';
+    this.pngOptions.style.display = shows('png') ? '' : 'none';
+    this.webpOptions.style.display = shows('webp') ? '' : 'none';
+  }
+
+  updateEncoding() {
+    this.currentSettings.encoding = {
+      format: this.outputFormat.value,
+      quality: Math.min(100, Math.max(1, parseInt(this.outputQuality.value) || 90)),
+      progressive: this.jpegProgressive.checked,
+      chromaSubsampling: this.chromaSubsampling.value,
+      pngCompression: Math.min(9, Math.max(0, parseInt(this.pngCompression.value) || 0)),
+      webpLossless: this.webpLossless.checked
+    };
+    localStorage.setItem('imageOverlayEncoding', JSON.stringify(this.currentSettings.encoding));
+    this.applyEncodingToControls();
+  }
+
+  // Rust 의 TextStyleOptions 형식으로 변환 (자동 줄바꿈 너비는 Rust 에서 이미지 너비 기준으로 계산)
+  textStyleOptions() {
+    const style = this.currentSettings.style;
+    return {
+      textColor: style.textColor,
+      backgroundColor: style.noBackground ? 'none' : style.backgroundColor,
+      backgroundOpacity: style.backgroundOpacity,
+      stroke: style.strokeEnabled ? { width: style.strokeWidth, color: style.strokeColor } : null,
+      shadow: style.shadowEnabled ? {} : null, // 기본 그림자 (오른쪽 아래, 반투명 검정)
+      glow: style.glowEnabled ? { color: style.glowColor } : null,
+      align: style.align,
+      lineHeight: style.lineHeight,
+      maxWidthPercent: style.wrapPercent > 0 ? style.wrapPercent : null,
+      rotation: style.rotation || null // 양수면 반시계 방향 (90 / 180 / 270 도는 흐려지지 않음)
+    };
+  }
+
+  // 설치된 폰트 목록 불러오기 (userFontDir: 사용자가 추가한 폰트 폴더)
+  async loadFonts(userFontDir = null) {
+    try {
+      const fonts = await invoke('list_fonts', { userFontDir });
+      
+      this.fontSelect.innerHTML = '<option value="">기본 폰트 (자동)</option>';
+      fonts.forEach(font => {
+        const option = document.createElement('option');
+        option.value = font.id;
+        option.textContent = `${font.localizedFamily || font.family} - ${font.style}`;
+        this.fontSelect.appendChild(option);
+      });
+      
+      // 저장된 폰트가 목록에 없으면 기본 폰트 사용
+      if (!fonts.some(font => font.id === this.currentSettings.fontId)) {
+        this.currentSettings.fontId = '';
+      }
+      this.fontSelect.value = this.currentSettings.fontId;
+    } catch (error) {
+      console.error('폰트 목록 불러오기 오류:', error);
+    }
+  }
+
+  async selectFontFolder() {
+    try {
+      const result = await dialog.open({
+        directory: true,
+        multiple: false,
+        title: '폰트 폴더 선택'
+      });
+
+      if (result) {
+        localStorage.setItem('imageOverlayFontDir', result);
+        await this.loadFonts(result);
+        this.showNotification('폰트 폴더가 추가되었습니다.', 'success');
+      }
+    } catch (error) {
+      console.error('폰트 폴더 선택 오류:', error);
+      this.showNotification('폴더 선택 중 오류가 발생했습니다.', 'error');
+    }
+  }
+
   async loadImages() {
     try {
-      const imageFiles = await invoke('get_image_files', { folderPath: this.inputPath });
-      this.images = imageFiles.map(file => {
+      const imageFiles = await invoke('get_image_files', { folderPath: this.inputPath, options: this.scanOptions() });
+      this.images = imageFiles.map(({ path: file, subfolder, format, warning }) => {
         const fullName = file.split('/').pop() || file.split('\\\\').pop();
         const nameWithoutExtension = this.removeFileExtension(fullName);
         
@@ -623,6 +1207,9 @@ class ImageOverlayApp {
         
         return {
           path: file,
+          subfolder, // 입력 폴더 기준 하위 폴더 (바로 아래면 null)
+          format, // 파일 내용으로 판별한 형식 (알 수 없으면 null)
+          warning, // 확장자와 실제 형식이 다를 때 안내
           name: fullName, // 원본 파일명 (확장자 포함)
           nameWithoutExtension: normalizedName, // 정규화된 파일명 (확장자 제거 + 한글 정규화)
           text: '',
@@ -634,7 +1221,13 @@ class ImageOverlayApp {
       });
 
       console.log(`${this.images.length}개의 이미지가 로드되었습니다:`, this.images);
-      this.showNotification(`${this.images.length}개의 이미지가 로드되었습니다.`, 'success');
+      const warned = this.images.filter(image => image.warning);
+      warned.forEach(image => console.warn(`${image.name}: ${image.warning}`));
+      if (warned.length > 0) {
+        this.showNotification(`${this.images.length}개의 이미지가 로드되었습니다. (${warned.length}개는 확장자와 실제 형식이 다르거나 형식을 알 수 없습니다)`, 'success');
+      } else {
+        this.showNotification(`${this.images.length}개의 이미지가 로드되었습니다.`, 'success');
+      }
       
       // 썸네일 비동기 로드 시작
       this.loadThumbnails();
@@ -964,23 +1557,22 @@ class ImageOverlayApp {
     console.log(`미리보기 업데이트: ${selectedImage.name}, 텍스트: "${previewText}"`);
     
     try {
-      // 이미지 크기 가져오기
-      const imageDimensions = await this.getImageDimensions(selectedImage.path);
-      const scaledFontSize = this.calculateScaledFontSize(imageDimensions, this.currentSettings.fontSize);
-      const position = this.calculateRightBasedPosition(imageDimensions, this.currentSettings.positionX, this.currentSettings.positionY, scaledFontSize, previewText);
-      
-      // 안전한 값으로 변환
-      const safeX = Math.max(0, Math.floor(position.x)) || 0;
-      const safeY = Math.max(0, Math.floor(position.y)) || 0;
-      const safeFontSize = Math.max(10, Math.floor(scaledFontSize)) || 20;
+      const fontSizing = this.fontSizing(this.currentSettings.fontSize);
+      const placement = this.textPlacement(this.currentSettings.positionX, this.currentSettings.positionY);
       
-      // 경량화된 미리보기 함수 사용
+      // 경량화된 미리보기 함수 사용 (폰트 크기는 Rust 에서 이미지 해상도에 맞춰 계산)
       const previewData = await invoke('get_image_preview_lightweight', {
         imagePath: selectedImage.path,
         text: previewText || selectedImage.nameWithoutExtension,
-        fontSize: safeFontSize,
-        positionX: safeX,
-        positionY: safeY
+        fontSize: fontSizing.value,
+        positionX: 0, // placement 를 넘기면 Rust 에서 위치 계산
+        positionY: 0,
+        fontId: this.currentSettings.fontId || null,
+        style: this.textStyleOptions(),
+        placement,
+        fontSizing,
+        watermark: this.watermarkOptions(),
+        tile: this.tileOptions()
       });
       
       this.previewImage.src = previewData;
@@ -1042,23 +1634,22 @@ class ImageOverlayApp {
     console.log(`개별 미리보기 업데이트: ${previewImage.name}, 텍스트: "${previewText}"`);
     
     try {
-      // 이미지 크기 가져오기
-      const imageDimensions = await this.getImageDimensions(previewImage.path);
-      const scaledFontSize = this.calculateScaledFontSize(imageDimensions, fontSize);
-      const position = this.calculateRightBasedPosition(imageDimensions, posX, posY, scaledFontSize, previewText);
-      
-      // 안전한 값으로 변환
-      const safeX = Math.max(0, Math.floor(position.x)) || 0;
-      const safeY = Math.max(0, Math.floor(position.y)) || 0;
-      const safeFontSize = Math.max(10, Math.floor(scaledFontSize)) || 20;
+      const fontSizing = this.fontSizing(fontSize);
+      const placement = this.textPlacement(posX, posY);
       
-      // 경량화된 미리보기 함수 사용
+      // 경량화된 미리보기 함수 사용 (폰트 크기는 Rust 에서 이미지 해상도에 맞춰 계산)
       const previewData = await invoke('get_image_preview_lightweight', {
         imagePath: previewImage.path,
         text: previewText || previewImage.nameWithoutExtension,
-        fontSize: safeFontSize,
-        positionX: safeX,
-        positionY: safeY
+        fontSize: fontSizing.value,
+        positionX: 0, // placement 를 넘기면 Rust 에서 위치 계산
+        positionY: 0,
+        fontId: this.currentSettings.fontId || null,
+        style: this.textStyleOptions(),
+        placement,
+        fontSizing,
+        watermark: this.watermarkOptions(),
+        tile: this.tileOptions()
       });
       
       this.indPreviewImage.src = previewData;
@@ -1081,7 +1672,7 @@ class ImageOverlayApp {
     }
   }
 
-  // 경량화된 일괄 처리
+  // 일괄 처리 (Rust 에서 여러 이미지를 병렬 처리)
   async processBatchImages() {
     const logs = [];
     
@@ -1091,53 +1682,23 @@ class ImageOverlayApp {
       // 캐시 클리어 (메모리 확보)
       this.clearCache();
       
-      for (let i = 0; i < this.images.length; i++) {
-        const image = this.images[i];
+      const jobs = this.images.map(image => {
         const text = this.isAutoText ? image.nameWithoutExtension : this.customText.value;
-        
-        try {
-          // 이미지 크기 가져오기
-          const imageDimensions = await this.getImageDimensions(image.path);
-          const scaledFontSize = this.calculateScaledFontSize(imageDimensions, this.currentSettings.fontSize);
-          const position = this.calculateRightBasedPosition(imageDimensions, this.currentSettings.positionX, this.currentSettings.positionY, scaledFontSize, text);
-          
-          console.log(`${image.name}: ${imageDimensions.width}x${imageDimensions.height}, 위치: (${position.x}, ${position.y}), 크기: ${scaledFontSize}px`);
-          
-          // 안전한 값으로 변환
-          const safeX = Math.max(0, Math.floor(position.x)) || 0;
-          const safeY = Math.max(0, Math.floor(position.y)) || 0;
-          const safeFontSize = Math.max(10, Math.floor(scaledFontSize)) || 20;
-          
-          // 경량화된 이미지 처리 함수 사용 (내부적으로 add_text_to_image_simple 사용)
-          const result = await invoke('process_image', {
-            imagePath: image.path,
-            outputPath: this.outputPath,
-            text: text || image.nameWithoutExtension,
-            fontSize: safeFontSize,
-            positionX: safeX,
-            positionY: safeY
-          });
-
-          if (result.success) {
-            logs.push({ type: 'success', message: `✓ ${image.name} 처리 완료` });
-          } else {
-            logs.push({ type: 'error', message: `✗ ${image.name} 처리 실패: ${result.error}` });
-          }
-        } catch (imageError) {
-          console.error(`${image.name} 처리 중 오류:`, imageError);
-          logs.push({ type: 'error', message: `✗ ${image.name} 처리 실패: ${imageError.message}` });
-        }
-      }
+        return { imagePath: image.path, text: text || image.nameWithoutExtension, subfolder: this.jobSubfolder(image) };
+      });
+      
+      const summary = await this.runBatch(jobs, this.currentSettings.fontSize, this.currentSettings.positionX, this.currentSettings.positionY);
+      logs.push(...this.batchLogs(this.images, summary));
     } catch (error) {
       console.error('일괄 처리 오류:', error);
-      logs.push({ type: 'error', message: `처리 중 오류 발생: ${error.message}` });
+      logs.push({ type: 'error', message: `처리 중 오류 발생: ${error.message || error}` });
     }
 
     this.closeBatchModalHandler();
     this.showProcessingResults(logs);
   }
 
-  // 경량화된 개별 처리
+  // 개별 처리 (선택한 이미지만, 이미지마다 다른 텍스트)
   async processIndividualImages() {
     const logs = [];
     
@@ -1159,43 +1720,14 @@ class ImageOverlayApp {
       const posX = parseInt(this.indPositionX.value);
       const posY = parseInt(this.indPositionY.value);
 
-      for (let i = 0; i < selectedImages.length; i++) {
-        const image = selectedImages[i];
-        const text = image.text || image.nameWithoutExtension;
-        
-        try {
-          // 이미지 크기 가져오기
-          const imageDimensions = await this.getImageDimensions(image.path);
-          const scaledFontSize = this.calculateScaledFontSize(imageDimensions, fontSize);
-          const position = this.calculateRightBasedPosition(imageDimensions, posX, posY, scaledFontSize, text);
-          
-          console.log(`${image.name}: ${imageDimensions.width}x${imageDimensions.height}, 위치: (${position.x}, ${position.y}), 크기: ${scaledFontSize}px`);
-          
-          // 안전한 값으로 변환
-          const safeX = Math.max(0, Math.floor(position.x)) || 0;
-          const safeY = Math.max(0, Math.floor(position.y)) || 0;
-          const safeFontSize = Math.max(10, Math.floor(scaledFontSize)) || 20;
-          
-          // 경량화된 이미지 처리 함수 사용
-          const result = await invoke('process_image', {
-            imagePath: image.path,
-            outputPath: this.outputPath,
-            text: text || image.nameWithoutExtension,
-            fontSize: safeFontSize,
-            positionX: safeX,
-            positionY: safeY
-          });
-
-          if (result.success) {
-            logs.push({ type: 'success', message: `✓ ${image.name} 처리 완료` });
-          } else {
-            logs.push({ type: 'error', message: `✗ ${image.name} 처리 실패: ${result.error}` });
-          }
-        } catch (imageError) {
-          console.error(`${image.name} 처리 중 오류:`, imageError);
-          logs.push({ type: 'error', message: `✗ ${image.name} 처리 실패: ${imageError.message}` });
-        }
-      }
+      const jobs = selectedImages.map(image => ({
+        imagePath: image.path,
+        text: image.text || image.nameWithoutExtension,
+        subfolder: this.jobSubfolder(image)
+      }));
+      
+      const summary = await this.runBatch(jobs, fontSize, posX, posY);
+      logs.push(...this.batchLogs(selectedImages, summary));
       
       // 선택되지 않은 이미지들도 로그에 추가
       const unselectedImages = this.images.filter(img => !img.selected);
@@ -1205,137 +1737,141 @@ class ImageOverlayApp {
       
     } catch (error) {
       console.error('개별 처리 오류:', error);
-      logs.push({ type: 'error', message: `처리 중 오류 발생: ${error.message}` });
+      logs.push({ type: 'error', message: `처리 중 오류 발생: ${error.message || error}` });
     }
 
     this.closeIndividualModalHandler();
     this.showProcessingResults(logs);
   }
 
-  // 이미지 크기 가져오기
-  async getImageDimensions(imagePath) {
+  // 공통 설정으로 작업 목록을 Rust 에 넘김 (이미지 디코딩, 폰트 크기/위치 계산은 모두 Rust 에서)
+  async runBatch(jobs, fontSize, marginXPercentage, marginYPercentage) {
+    const fontSizing = this.fontSizing(fontSize);
+    const names = jobs.map(job => job.imagePath.split(/[\\/]/).pop());
+    
+    this.openProgressModal(jobs.length);
+    const unlisten = await event.listen('batch-progress', ({ payload }) => this.updateProgress(payload, names));
+    
     try {
-      return await invoke('get_image_dimensions', { imagePath });
+      return await invoke('process_batch', {
+        jobs,
+        options: {
+          outputPath: this.outputPath,
+          overwrite: this.currentSettings.overwrite,
+          fileNameTemplate: this.currentSettings.fileNameTemplate || null,
+          encoding: this.currentSettings.encoding,
+          metadata: this.currentSettings.metadata,
+          fontSize: fontSizing.value,
+          fontSizing,
+          placement: this.textPlacement(marginXPercentage, marginYPercentage),
+          fontId: this.currentSettings.fontId || null,
+          style: this.textStyleOptions(),
+          watermark: this.watermarkOptions(),
+          tile: this.tileOptions()
+        }
+      });
+    } finally {
+      unlisten();
+      this.closeProgressModal();
+    }
+  }
+
+  // 진행 중인 일괄 처리 중지 (저장 전인 이미지는 건너뜀)
+  async cancelBatch() {
+    this.stopBatchBtn.disabled = true;
+    this.stopBatchBtn.textContent = '중지하는 중...';
+    try {
+      await invoke('cancel_batch');
     } catch (error) {
-      console.error('이미지 크기 가져오기 오류:', error);
-      // 기본값 반환
-      return { width: 400, height: 300 };
+      console.error('처리 중지 실패:', error);
     }
   }
 
-  // 🔧 텍스트 크기 일관성 개선: 더 일관된 상대적 크기 계산
-  calculateScaledFontSize(imageDimensions, baseFontSize) {
-    // 상대적 크기 모드: 이미지 크기에 비례하되 더 일관된 기준 사용
-    
-    // 기준 이미지 면적 (400x300 = 120,000)
-    const baseImageArea = 400 * 300;
-    
-    // 현재 이미지 면적
-    const currentImageArea = imageDimensions.width * imageDimensions.height;
-    
-    // 면적 비율의 제곱근으로 스케일 팩터 계산 (더 일관된 시각적 결과)
-    const scaleFactor = Math.sqrt(currentImageArea / baseImageArea);
-    
-    // 스케일 팩터를 적당한 범위로 제한 (너무 크거나 작지 않게)
-    const limitedScaleFactor = Math.max(0.5, Math.min(scaleFactor, 3.0));
-    
-    // 최종 폰트 크기 계산
-    const scaledSize = baseFontSize * limitedScaleFactor;
-    
-    // 안전한 범위 내에서 반환
-    return Math.max(10, Math.min(Math.round(scaledSize), 200));
+  openProgressModal(total) {
+    this.progressFill.style.width = '0%';
+    this.progressCounts.textContent = `0 / ${total}`;
+    this.progressEta.textContent = '';
+    this.progressCurrent.textContent = '준비 중...';
+    this.stopBatchBtn.disabled = false;
+    this.stopBatchBtn.textContent = '처리 중지';
+    this.progressModal.style.display = 'block';
   }
 
-  // 🔧 텍스트 박스 문제 해결: 더 정확한 위치 계산
-  calculateRightBasedPosition(imageDimensions, rightPercentage, bottomPercentage, fontSize = 20, text = 'Sample') {
-    // 🔧 더 정확한 텍스트 크기 추정
-    const estimatedTextWidth = this.estimateTextWidth(text, fontSize);
-    const estimatedTextHeight = fontSize; // 행간 제거하여 높이 축소
-    const padding = 2; // 4 -> 2로 패딩 축소
-    
-    // 🔧 우측 기준 계산: 텍스트 끝이 정확한 위치에
-    let x = imageDimensions.width * (1 - rightPercentage / 100) - estimatedTextWidth;
-    
-    // 🔧 하단 기준 계산
-    let y = imageDimensions.height * (1 - bottomPercentage / 100) - estimatedTextHeight;
-    
-    // 🔧 안전 장치: 텍스트가 이미지 경계를 벗어나지 않도록
-    x = Math.max(padding, Math.min(x, imageDimensions.width - estimatedTextWidth - padding));
-    y = Math.max(padding, Math.min(y, imageDimensions.height - estimatedTextHeight - padding));
-    
-    return {
-      x: Math.round(x),
-      y: Math.round(y)
-    };
+  closeProgressModal() {
+    this.progressModal.style.display = 'none';
   }
-  
-  // 🔧 텍스트 박스 문제 해결: Rust와 완전히 일치하는 너비 계산
-  estimateTextWidth(text, fontSize) {
-    if (!text) return 10; // 최소 너비
-    
-    let totalWidth = 0;
-    const charCount = text.length;
+
+  // batch-progress 이벤트 (작업마다 started 와 결과 이벤트가 옴)
+  updateProgress(progress, names) {
+    const name = names[progress.index] || progress.imagePath;
+    this.progressFill.style.width = `${progress.total > 0 ? progress.completed / progress.total * 100 : 100}%`;
+    this.progressCounts.textContent = `${progress.completed} / ${progress.total} (성공 ${progress.succeeded}, 실패 ${progress.failed}, 건너뜀 ${progress.skipped})`;
+    if (progress.etaMs != null) {
+      this.progressEta.textContent = `남은 시간: 약 ${this.formatDuration(progress.etaMs)}`;
+    }
     
-    for (let i = 0; i < text.length; i++) {
-      const char = text[i];
-      let charWidth;
-      
-      // 🔧 Rust와 적확히 동일한 문자 너비 계산
-      if (char === '1') {
-        charWidth = fontSize * 0.2; // 1은 특별히 좁음
-      } else if (['i', 'l', '!', '|', 'I', 'j', '.', ','].includes(char)) {
-        charWidth = fontSize * 0.25; // 매우 좁은 문자
-      } else if (['t', 'f', 'r', "'", '"'].includes(char)) {
-        charWidth = fontSize * 0.35; // 조금 증가
-      } else if (['W', 'M'].includes(char)) {
-        charWidth = fontSize * 0.7; // 0.8 -> 0.7
-      } else if (['w', 'm'].includes(char)) {
-        charWidth = fontSize * 0.65; // 0.75 -> 0.65
-      } else if (char === ' ') {
-        charWidth = fontSize * 0.2; // 0.25 -> 0.2
-      } else if (char >= '가' && char <= '힣') {
-        charWidth = fontSize * 0.7; // 한글 완성형 0.8 -> 0.7
-      } else if (char >= 'ㄱ' && char <= 'ㅎ') {
-        charWidth = fontSize * 0.4; // 한글 자음 0.5 -> 0.4
-      } else if (char >= 'ㅏ' && char <= 'ㅣ') {
-        charWidth = fontSize * 0.3; // 한글 모음 0.4 -> 0.3
-      } else if (char >= 'ㅤ' && char <= 'ㆎ') {
-        charWidth = fontSize * 0.45; // 한글 확장 0.55 -> 0.45
-      } else if (['0', '8'].includes(char)) {
-        charWidth = fontSize * 0.5; // 0.6 -> 0.5
-      } else if (char >= '2' && char <= '7' || char === '9') {
-        charWidth = fontSize * 0.45; // 0.55 -> 0.45
-      } else if (['A', 'H', 'N', 'U', 'V', 'X', 'Y', 'Z'].includes(char)) {
-        charWidth = fontSize * 0.55; // 0.65 -> 0.55
-      } else if (['Q', 'G', 'O', 'D'].includes(char)) {
-        charWidth = fontSize * 0.6; // 0.7 -> 0.6
-      } else if (['B', 'C', 'E', 'F', 'K', 'L', 'P', 'R', 'S', 'T', 'J'].includes(char)) {
-        charWidth = fontSize * 0.5; // 0.6 -> 0.5
-      } else if (['a', 'c', 'e', 'g', 'o', 'q', 's'].includes(char)) {
-        charWidth = fontSize * 0.5; // 0.4 -> 0.5로 증가
-      } else if (['b', 'd', 'h', 'k', 'n', 'p', 'u', 'v', 'x', 'y', 'z'].includes(char)) {
-        charWidth = fontSize * 0.5; // 0.45 -> 0.5로 증가
-      } else if (char === '-') {
-        charWidth = fontSize * 0.35; // 하이픈은 특히 좁음
-      } else if (['_', '=', '+'].includes(char)) {
-        charWidth = fontSize * 0.4; // 0.45 -> 0.4
-      } else if (['@', '%', '#', '&'].includes(char)) {
-        charWidth = fontSize * 0.6; // 0.7 -> 0.6
-      } else {
-        charWidth = fontSize * 0.45; // 기본값 0.55 -> 0.45
+    switch (progress.kind) {
+      case 'started':
+        this.progressCurrent.textContent = `처리 중: ${name}`;
+        break;
+      case 'failed':
+        this.progressCurrent.textContent = `실패: ${name} - ${progress.error}`;
+        break;
+      case 'skipped':
+        this.progressCurrent.textContent = `건너뜀: ${name}`;
+        break;
+    }
+  }
+
+  formatDuration(ms) {
+    const seconds = Math.ceil(ms / 1000);
+    return seconds < 60 ? `${seconds}초` : `${Math.floor(seconds / 60)}분 ${seconds % 60}초`;
+  }
+
+  // 작업 결과를 처리 결과 로그로 변환 (results 는 작업 목록과 같은 순서)
+  batchLogs(images, summary) {
+    const logs = summary.results.map((result, index) => {
+      const image = images[index];
+      switch (result.status) {
+        case 'succeeded': {
+          const savedAs = result.outputFile ? result.outputFile.split(/[\\/]/).pop() : image.name;
+          const renamed = savedAs !== image.name ? ` → ${savedAs}` : '';
+          return { type: 'success', message: `✓ ${image.name} 처리 완료${renamed}${result.fontUsed ? ` (폰트: ${result.fontUsed})` : ''}` };
+        }
+        case 'skipped':
+          return { type: 'info', message: `⏭️ ${image.name} 건너뛰기 (${result.error})` };
+        default:
+          return { type: 'error', message: `✗ ${image.name} 처리 실패: ${result.error}` };
       }
-      
-      totalWidth += charWidth;
-      
-      // 🔧 문자 간 간격 완전 제거 (Rust와 동일)
-      // 간격을 추가하지 않음
+    });
+    if (summary.cancelled) {
+      logs.unshift({ type: 'info', message: `처리를 중지했습니다. (완료: ${summary.succeeded}개, 실패: ${summary.failed}개, 건너뛰기: ${summary.skipped}개)` });
     }
-    
-    // 🔧 여유 공간 약간 추가 (Rust와 동일)
-    const finalWidth = totalWidth * 1.1; // 10% 여유 추가
-    
-    // 최소/최대 제한
-    return Math.round(Math.max(10, Math.min(finalWidth, fontSize * text.length * 0.8)));
+    return logs;
+  }
+
+  applyFontSizeModeToControls() {
+    const mode = this.currentSettings.fontSizeMode;
+    const unit = mode === 'auto' || mode === 'px' ? 'px' : '%';
+    this.fontSizeMode.value = mode;
+    this.indFontSizeMode.value = mode;
+    this.fontSizeUnit.textContent = unit;
+    this.indFontSizeUnit.textContent = unit;
+  }
+
+  // Rust 에서 이미지를 읽은 뒤 해상도에 맞춰 폰트 크기 계산 (auto: 400x300 기준 면적 비율로 확대/축소)
+  fontSizing(value) {
+    return { mode: this.currentSettings.fontSizeMode, value: Math.max(1, value) || 5 };
+  }
+
+  // 🔧 텍스트 위치: 기준점 + 이미지 크기 대비 여백(%)만 넘기고 실제 좌표는 Rust 에서 측정한 텍스트 영역으로 계산
+  textPlacement(marginXPercentage, marginYPercentage) {
+    return {
+      anchor: this.currentSettings.anchor,
+      marginX: marginXPercentage,
+      marginY: marginYPercentage,
+      marginUnit: 'percent'
+    };
   }
 
   showProcessingResults(logs) {